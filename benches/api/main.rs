@@ -9,9 +9,11 @@ criterion_group!(
     set::build,
     set::contains,
     set::range,
+    set::len,
     map::build,
     map::contains_key,
     map::get,
+    map::iter_filter,
     map::range
 );
 criterion_main!(benches);
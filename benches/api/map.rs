@@ -122,6 +122,32 @@ pub fn get(c: &mut Criterion) {
     group.finish();
 }
 
+pub fn iter_filter(c: &mut Criterion) {
+    let cells = load_dataset("Paris");
+    let expanded = FrozenMap::try_from_iter(
+        CellIndex::uncompact(cells, Resolution::Ten)
+            .enumerate()
+            .map(|(idx, cell)| (cell, idx as u64)),
+    )
+    .expect("expanded map");
+    // Selective predicate: about 1% of the entries match.
+    let pred = |value: u64| value % 100 == 0;
+
+    let mut group = c.benchmark_group("IterFilter/FrozenMap");
+    group.bench_function("IterFilter", |b| {
+        b.iter(|| expanded.iter_filter(pred).for_each(drop))
+    });
+    group.bench_function("IterThenFilter", |b| {
+        b.iter(|| {
+            expanded
+                .iter()
+                .filter(|&(_, value)| pred(value))
+                .for_each(drop)
+        })
+    });
+    group.finish();
+}
+
 pub fn range(c: &mut Criterion) {
     let cells = load_dataset("France");
     let expanded = FrozenMap::try_from_iter(
@@ -36,6 +36,9 @@ pub fn contains(c: &mut Criterion) {
 
     let expanded_frozen = FrozenSet::try_from_iter(expanded.iter().copied())
         .expect("expanded set");
+    let expanded_single =
+        FrozenSet::new_single_resolution(expanded_frozen.as_bytes())
+            .expect("single resolution set");
     let expanded_hash = expanded.iter().copied().collect::<HashSet<_>>();
     let expanded_tree = expanded.iter().copied().collect::<BTreeSet<_>>();
 
@@ -60,6 +63,11 @@ pub fn contains(c: &mut Criterion) {
             cell,
             |b, cell| b.iter(|| expanded_frozen.contains(*cell)),
         );
+        group.bench_with_input(
+            BenchmarkId::new("Expanded/SingleResolutionSet", i),
+            cell,
+            |b, cell| b.iter(|| expanded_single.contains(*cell)),
+        );
         group.bench_with_input(
             BenchmarkId::new("Compacted/FrozenSet", i),
             cell,
@@ -120,6 +128,22 @@ pub fn range(c: &mut Criterion) {
     group.finish();
 }
 
+pub fn len(c: &mut Criterion) {
+    let dataset = load_dataset("France");
+    let expanded = FrozenSet::try_from_iter(CellIndex::uncompact(
+        dataset,
+        Resolution::Ten,
+    ))
+    .expect("expanded set");
+
+    let mut group = c.benchmark_group("Len/FrozenSet");
+    group.bench_function("len", |b| b.iter(|| black_box(&expanded).len()));
+    group.bench_function("is_empty", |b| {
+        b.iter(|| black_box(&expanded).is_empty())
+    });
+    group.finish();
+}
+
 fn hashset_contains(
     set: &HashSet<CellIndex>,
     index: CellIndex,
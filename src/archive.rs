@@ -0,0 +1,219 @@
+use crate::{BuildError, FrozenSet};
+use std::{io, ops::Range};
+
+// Magic number, at the start of every archive.
+const MAGIC: &[u8; 8] = b"H3OICEAR";
+// Version of the archive format.
+const VERSION: u64 = 1;
+// Size of an integer in the archive directory, in bytes.
+const INT_SIZE: usize = 8;
+
+/// A builder for creating an archive of named sets.
+///
+/// The archive starts with a header (magic number and format version) and a
+/// directory, mapping each name to the location of its set, followed by the
+/// sets themselves. Since the directory must be
+/// written first, sets are buffered in memory until `finish` is called.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{CellIndex, Resolution};
+/// use h3o_ice::{Archive, ArchiveBuilder, FrozenSet};
+///
+/// let index = CellIndex::try_from(0x85318d83fffffff)?;
+/// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+///
+/// let mut builder = ArchiveBuilder::new(Vec::new());
+/// builder.add_set("coverage", &set);
+/// let bytes = builder.finish()?;
+///
+/// let archive = Archive::open(bytes)?;
+/// let coverage = archive.get_set("coverage")?.expect("archived set");
+/// assert_eq!(coverage.len(), set.len());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct ArchiveBuilder<W> {
+    wtr: W,
+    sets: Vec<(String, Vec<u8>)>,
+}
+
+impl<W: io::Write> ArchiveBuilder<W> {
+    /// Create a builder that writes the archive to the given writer.
+    pub const fn new(wtr: W) -> Self {
+        Self {
+            wtr,
+            sets: Vec::new(),
+        }
+    }
+
+    /// Adds a named set to the archive.
+    ///
+    /// If several sets share the same name, only the first one is reachable
+    /// with `Archive::get_set`.
+    pub fn add_set<D: AsRef<[u8]>>(&mut self, name: &str, set: &FrozenSet<D>) {
+        self.sets.push((name.to_owned(), set.fst_bytes().to_vec()));
+    }
+
+    /// Writes the archive and returns the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// If there was a problem writing to the underlying writer,
+    /// `BuildError::Io` is returned.
+    pub fn finish(mut self) -> Result<W, BuildError> {
+        self.write()?;
+        Ok(self.wtr)
+    }
+
+    fn write(&mut self) -> io::Result<()> {
+        self.wtr.write_all(MAGIC)?;
+        self.wtr.write_all(&VERSION.to_le_bytes())?;
+        write_int(&mut self.wtr, self.sets.len())?;
+        let mut offset = 0;
+        for set in &self.sets {
+            write_int(&mut self.wtr, set.0.len())?;
+            self.wtr.write_all(set.0.as_bytes())?;
+            write_int(&mut self.wtr, offset)?;
+            write_int(&mut self.wtr, set.1.len())?;
+            offset += set.1.len();
+        }
+        for set in &self.sets {
+            self.wtr.write_all(&set.1)?;
+        }
+        self.wtr.flush()
+    }
+}
+
+/// A read-only archive of named sets.
+pub struct Archive<D> {
+    data: D,
+    // Name and location of each set.
+    sets: Vec<(String, Range<usize>)>,
+}
+
+impl<D: AsRef<[u8]>> Archive<D> {
+    /// Opens an archive from its representation as a raw byte sequence.
+    ///
+    /// Note that only the directory is checked here, the sets themselves are
+    /// validated when retrieved.
+    ///
+    /// # Errors
+    ///
+    /// If the archive was written with an incompatible format version,
+    /// `BuildError::VersionMismatch` is returned.
+    ///
+    /// If the header or the directory is invalid, `BuildError::InvalidArchive`
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use h3o_ice::Archive;
+    /// use std::fs;
+    ///
+    /// # let file_path = "";
+    /// let bytes = fs::read(file_path)?;
+    /// let archive = Archive::open(bytes)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn open(data: D) -> Result<Self, BuildError> {
+        let sets = parse_directory(data.as_ref())?;
+        Ok(Self { data, sets })
+    }
+
+    /// Returns the set with the given name, if any.
+    ///
+    /// # Errors
+    ///
+    /// If the named set is invalid, an error is returned.
+    pub fn get_set(
+        &self,
+        name: &str,
+    ) -> Result<Option<FrozenSet<&[u8]>>, BuildError> {
+        self.sets
+            .iter()
+            .find(|set| set.0 == name)
+            .map(|set| FrozenSet::new(&self.data.as_ref()[set.1.clone()]))
+            .transpose()
+    }
+
+    /// Returns the names of the archived sets, in insertion order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.sets.iter().map(|set| set.0.as_str())
+    }
+}
+
+fn write_int(wtr: &mut impl io::Write, value: usize) -> io::Result<()> {
+    wtr.write_all(&(value as u64).to_le_bytes())
+}
+
+/// Checks the header and parses the directory, returning the absolute
+/// location of each set.
+fn parse_directory(
+    data: &[u8],
+) -> Result<Vec<(String, Range<usize>)>, BuildError> {
+    let mut reader = Reader { data, pos: 0 };
+
+    if reader.read(MAGIC.len()) != Some(MAGIC.as_slice()) {
+        return Err(BuildError::InvalidArchive);
+    }
+    let version = reader.read_u64().ok_or(BuildError::InvalidArchive)?;
+    if version != VERSION {
+        return Err(BuildError::VersionMismatch {
+            expected: VERSION,
+            found: version,
+        });
+    }
+
+    parse_entries(reader).ok_or(BuildError::InvalidArchive)
+}
+
+/// Parses the directory entries, returning the absolute location of each set.
+fn parse_entries(
+    mut reader: Reader<'_>,
+) -> Option<Vec<(String, Range<usize>)>> {
+    let data = reader.data;
+    let count = reader.read_int()?;
+    let mut sets = Vec::new();
+    for _ in 0..count {
+        let len = reader.read_int()?;
+        let name = String::from_utf8(reader.read(len)?.to_vec()).ok()?;
+        let offset = reader.read_int()?;
+        let len = reader.read_int()?;
+        sets.push((name, offset, len));
+    }
+
+    // Sets are stored right after the directory.
+    let start = reader.pos;
+    sets.into_iter()
+        .map(|(name, offset, len)| {
+            let begin = start.checked_add(offset)?;
+            let end = begin.checked_add(len)?;
+            (end <= data.len()).then_some((name, begin..end))
+        })
+        .collect()
+}
+
+/// A cursor over the archive directory.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn read_u64(&mut self) -> Option<u64> {
+        let bytes = self.read(INT_SIZE)?.try_into().ok()?;
+        Some(u64::from_le_bytes(bytes))
+    }
+
+    fn read_int(&mut self) -> Option<usize> {
+        usize::try_from(self.read_u64()?).ok()
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{BuildError, FrozenMap};
+use h3o::CellIndex;
+
+/// A read-only map of H3 cell indexes, with its values stored in a
+/// contiguous array.
+///
+/// The keys are kept in an FST whose values are positions into the array.
+/// This makes scanning the values as cheap as scanning a slice, at the cost
+/// of an extra indirection for the lookups.
+///
+/// Created by `FrozenMap::to_columnar`.
+///
+/// # Example
+///
+/// ```
+/// use h3o::{CellIndex, Resolution};
+/// use h3o_ice::FrozenMap;
+///
+/// let index = CellIndex::try_from(0x85318d83fffffff)?;
+/// let map = FrozenMap::try_from_iter(
+///     index.children(Resolution::Six).map(|cell| (cell, 42)),
+/// )?;
+///
+/// let columnar = map.to_columnar()?;
+/// assert_eq!(columnar.values().iter().sum::<u64>(), 7 * 42);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct ColumnarMap {
+    positions: FrozenMap<Vec<u8>>,
+    values: Vec<u64>,
+}
+
+impl ColumnarMap {
+    pub(crate) fn new<D: AsRef<[u8]>>(
+        map: &FrozenMap<D>,
+    ) -> Result<Self, BuildError> {
+        let mut position = 0;
+        let positions = map.map_values(|_| {
+            position += 1;
+            position - 1
+        })?;
+
+        Ok(Self {
+            positions,
+            values: map.values_columnar(),
+        })
+    }
+
+    /// Returns the number of elements in this map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns true if and only if this map is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Retrieves the value associated with a cell index.
+    ///
+    /// Just like `FrozenMap::get`, the value of the matching ancestor is
+    /// returned if the cell index itself isn't present.
+    #[must_use]
+    pub fn get(&self, index: CellIndex) -> Option<(CellIndex, u64)> {
+        let (cell, position) = self.positions.get(index)?;
+        let value = self.values.get(usize::try_from(position).ok()?)?;
+        Some((cell, *value))
+    }
+
+    /// Returns every value of this map, in the lexicographic order of their
+    /// keys.
+    #[must_use]
+    pub fn values(&self) -> &[u64] {
+        &self.values
+    }
+}
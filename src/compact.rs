@@ -0,0 +1,149 @@
+use h3o::CellIndex;
+
+/// Compacts a lexicographically ordered stream of cell indexes.
+///
+/// Unlike `CellIndex::compact`, the input can mix resolutions: cells covered
+/// by one of their ancestors are dropped, and complete sets of siblings are
+/// recursively replaced by their parent.
+///
+/// The output is in lexicographic order as well.
+pub fn compact(cells: impl IntoIterator<Item = CellIndex>) -> Vec<CellIndex> {
+    let mut stack: Vec<CellIndex> = Vec::new();
+
+    for cell in cells {
+        // In lexicographic order, an ancestor is visited right before its
+        // descendants.
+        if stack.last().is_some_and(|last| is_ancestor(*last, cell)) {
+            continue;
+        }
+        stack.push(cell);
+
+        while let Some(parent) = collapsible_parent(&stack) {
+            let count = children_count(parent);
+            stack.truncate(stack.len() - count);
+            stack.push(parent);
+        }
+    }
+
+    stack
+}
+
+/// Returns true if a lexicographically ordered stream of cell indexes is
+/// compacted, i.e. if `compact` would leave it unchanged.
+///
+/// The check is done in a single pass, in constant memory, and stops at the
+/// first cell that would be dropped or merged.
+pub fn is_compacted(cells: impl IntoIterator<Item = CellIndex>) -> bool {
+    let mut previous: Option<CellIndex> = None;
+    // Length of the current run of consecutive siblings.
+    let mut siblings = 0;
+
+    for cell in cells {
+        // In lexicographic order, an ancestor is visited right before its
+        // descendants.
+        if previous.is_some_and(|previous| is_ancestor(previous, cell)) {
+            return false;
+        }
+
+        // Without covered cells, siblings are visited consecutively.
+        let parent = cell.resolution().pred().and_then(|res| cell.parent(res));
+        let is_sibling =
+            previous.zip(parent).is_some_and(|(previous, parent)| {
+                previous.resolution() == cell.resolution()
+                    && previous.parent(parent.resolution()) == Some(parent)
+            });
+        siblings = if is_sibling { siblings + 1 } else { 1 };
+        if parent.is_some_and(|parent| siblings == children_count(parent)) {
+            return false;
+        }
+
+        previous = Some(cell);
+    }
+
+    true
+}
+
+/// Returns true if `ancestor` is a strict ancestor of `cell`.
+pub fn is_ancestor(ancestor: CellIndex, cell: CellIndex) -> bool {
+    ancestor.resolution() < cell.resolution()
+        && cell.parent(ancestor.resolution()) == Some(ancestor)
+}
+
+/// Returns the parent of the top of the stack if all its children are at the
+/// top of the stack.
+fn collapsible_parent(stack: &[CellIndex]) -> Option<CellIndex> {
+    let last = *stack.last()?;
+    let parent = last.resolution().pred().and_then(|res| last.parent(res))?;
+    let count = children_count(parent);
+
+    // Cells are unique and ordered, thus `count` children of the same parent
+    // means every child is present.
+    (stack.len() >= count
+        && stack[stack.len() - count..].iter().all(|cell| {
+            cell.resolution() == last.resolution()
+                && cell.parent(parent.resolution()) == Some(parent)
+        }))
+    .then_some(parent)
+}
+
+/// Returns the number of direct children of a cell.
+fn children_count(cell: CellIndex) -> usize {
+    cell.resolution().succ().map_or(0, |res| {
+        usize::try_from(cell.children_count(res)).expect("at most 7 children")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use h3o::Resolution;
+
+    #[test]
+    fn test_compact() {
+        let parent =
+            CellIndex::try_from(0x85318d83fffffff).expect("valid cell");
+        let other = CellIndex::try_from(0x85318d93fffffff).expect("valid cell");
+        let cells = parent
+            .children(Resolution::Seven)
+            .chain(other.children(Resolution::Six).skip(1));
+
+        let mut expected = vec![parent];
+        expected.extend(other.children(Resolution::Six).skip(1));
+        assert_eq!(compact(cells), expected);
+    }
+
+    #[test]
+    fn test_is_compacted() {
+        let parent =
+            CellIndex::try_from(0x85318d83fffffff).expect("valid cell");
+        let other = CellIndex::try_from(0x85318d93fffffff).expect("valid cell");
+
+        assert!(is_compacted(parent.children(Resolution::Six).skip(1)));
+        assert!(!is_compacted(parent.children(Resolution::Six)));
+        assert!(!is_compacted(
+            std::iter::once(parent).chain(parent.children(Resolution::Seven))
+        ));
+        // A complete group of siblings after an incomplete one.
+        let cells = parent
+            .children(Resolution::Six)
+            .skip(1)
+            .chain(other.children(Resolution::Six));
+        assert!(!is_compacted(cells));
+        // Complete groups at a finer resolution, below a single cell.
+        let cells = parent
+            .children(Resolution::Six)
+            .skip(1)
+            .flat_map(|cell| cell.children(Resolution::Seven).skip(1));
+        assert!(is_compacted(cells));
+    }
+
+    #[test]
+    fn test_compact_overlap() {
+        let parent =
+            CellIndex::try_from(0x85318d83fffffff).expect("valid cell");
+        let cells = std::iter::once(parent)
+            .chain(parent.children(Resolution::Eight).take(10));
+
+        assert_eq!(compact(cells), vec![parent]);
+    }
+}
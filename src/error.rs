@@ -1,4 +1,5 @@
-use std::{error::Error, fmt};
+use h3o::{error::InvalidCellIndex, CellIndex, Resolution};
+use std::{collections::TryReserveError, error::Error, fmt, io};
 
 /// Errors occurring while building a set or a map.
 #[derive(Debug)]
@@ -6,12 +7,99 @@ use std::{error::Error, fmt};
 pub enum BuildError {
     /// Failed to build the underlying FST.
     Fst(fst::Error),
+    /// Failed to read the input or write the output.
+    Io(io::Error),
+    /// The data doesn't match its checksum (or doesn't have one).
+    ChecksumMismatch,
+    /// Failed to allocate memory.
+    Alloc(TryReserveError),
+    /// Fewer values than cells were provided.
+    MissingValues,
+    /// The data was written with an incompatible format version.
+    VersionMismatch {
+        /// The format version supported by this library.
+        expected: u64,
+        /// The format version of the data.
+        found: u64,
+    },
+    /// A cell index was inserted after a greater one.
+    OutOfOrder {
+        /// The previously inserted cell index.
+        previous: CellIndex,
+        /// The cell index that was rejected.
+        attempted: CellIndex,
+    },
+    /// The archive directory is invalid.
+    InvalidArchive,
+    /// The packed cells are invalid.
+    InvalidPackedCells,
+    /// A group of cells is out of order, or contains cells from another base
+    /// cell.
+    InvalidGroup {
+        /// The base cell of the rejected group.
+        base_cell: u8,
+    },
+    /// A key doesn't decode to a valid cell index.
+    InvalidKey,
+    /// The set contains cells at different resolutions.
+    MixedResolutions,
+    /// A cell index at an unexpected resolution was inserted.
+    ResolutionMismatch {
+        /// The resolution expected by the builder.
+        expected: Resolution,
+        /// The cell index that was rejected.
+        attempted: CellIndex,
+    },
+    /// A line of the input isn't a valid cell index.
+    InvalidCell {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The parsing error.
+        source: InvalidCellIndex,
+    },
+    /// The geometry is invalid.
+    #[cfg(feature = "geo")]
+    InvalidGeometry(h3o::error::InvalidGeometry),
 }
 
 impl fmt::Display for BuildError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Self::Fst(ref err) => write!(f, "FST error: {err}"),
+            Self::Io(ref err) => write!(f, "I/O error: {err}"),
+            Self::ChecksumMismatch => write!(f, "checksum mismatch"),
+            Self::Alloc(ref err) => write!(f, "allocation error: {err}"),
+            Self::MissingValues => write!(f, "fewer values than cells"),
+            Self::VersionMismatch { expected, found } => write!(
+                f,
+                "format version mismatch: expected {expected}, found {found}"
+            ),
+            Self::OutOfOrder {
+                previous,
+                attempted,
+            } => {
+                write!(f, "out of order: {attempted} inserted after {previous}")
+            }
+            Self::InvalidArchive => write!(f, "invalid archive"),
+            Self::InvalidPackedCells => write!(f, "invalid packed cells"),
+            Self::InvalidGroup { base_cell } => {
+                write!(f, "invalid group for base cell {base_cell}")
+            }
+            Self::InvalidKey => write!(f, "invalid key"),
+            Self::MixedResolutions => write!(f, "mixed resolutions"),
+            Self::ResolutionMismatch {
+                expected,
+                attempted,
+            } => {
+                write!(f, "resolution mismatch: {attempted} not at {expected}")
+            }
+            Self::InvalidCell { line, ref source } => {
+                write!(f, "invalid cell at line {line}: {source}")
+            }
+            #[cfg(feature = "geo")]
+            Self::InvalidGeometry(ref err) => {
+                write!(f, "invalid geometry: {err}")
+            }
         }
     }
 }
@@ -20,12 +108,54 @@ impl Error for BuildError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match *self {
             Self::Fst(ref err) => Some(err),
+            Self::Io(ref err) => Some(err),
+            Self::Alloc(ref err) => Some(err),
+            Self::InvalidCell { ref source, .. } => Some(source),
+            #[cfg(feature = "geo")]
+            Self::InvalidGeometry(ref err) => Some(err),
+            Self::ChecksumMismatch
+            | Self::MissingValues
+            | Self::VersionMismatch { .. }
+            | Self::OutOfOrder { .. }
+            | Self::InvalidArchive
+            | Self::InvalidPackedCells
+            | Self::InvalidGroup { .. }
+            | Self::InvalidKey
+            | Self::MixedResolutions
+            | Self::ResolutionMismatch { .. } => None,
         }
     }
 }
 
 impl From<fst::Error> for BuildError {
     fn from(err: fst::Error) -> Self {
-        Self::Fst(err)
+        match err {
+            fst::Error::Fst(fst::raw::Error::Version { expected, got }) => {
+                Self::VersionMismatch {
+                    expected,
+                    found: got,
+                }
+            }
+            err => Self::Fst(err),
+        }
+    }
+}
+
+impl From<io::Error> for BuildError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<TryReserveError> for BuildError {
+    fn from(err: TryReserveError) -> Self {
+        Self::Alloc(err)
+    }
+}
+
+#[cfg(feature = "geo")]
+impl From<h3o::error::InvalidGeometry> for BuildError {
+    fn from(err: h3o::error::InvalidGeometry) -> Self {
+        Self::InvalidGeometry(err)
     }
 }
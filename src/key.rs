@@ -1,4 +1,5 @@
-use h3o::{CellIndex, Resolution};
+use h3o::{BaseCell, CellIndex, Resolution};
+use std::cmp::Ordering;
 
 // Max key size, in bytes (base cell + 15 children).
 const SIZE: usize = 16;
@@ -13,6 +14,34 @@ impl Key {
     const fn len(self) -> u8 {
         (15 - (u128::from_be_bytes(self.0).trailing_ones() / 8)) as u8
     }
+
+    /// Returns the bounds of the key range covering the descendants of this
+    /// key: the first child (inclusive) and the end of the subtree
+    /// (exclusive), or `None` at the finest resolution.
+    pub fn descendant_bounds(self) -> Option<(Self, Self)> {
+        // Directions range from 0 (center) to 6, thus 7 is past the last child
+        // and every one of its descendants.
+        let idx = usize::from(self.len()) + 1;
+        (idx < SIZE).then(|| {
+            let (mut start, mut end) = (self.0, self.0);
+            start[idx] = 0;
+            end[idx] = 7;
+            (Self(start), Self(end))
+        })
+    }
+
+    /// Decodes raw key bytes, or returns `None` if they don't encode a valid
+    /// cell index.
+    pub fn try_decode(bytes: &[u8]) -> Option<CellIndex> {
+        if bytes.len() > SIZE {
+            return None;
+        }
+        // Also rules out an all-0xff key, which has no length.
+        BaseCell::try_from(*bytes.first()?).ok()?;
+        let cell = CellIndex::try_from(u64::from(Self::from(bytes))).ok()?;
+        // Out of range bytes may still decode to a valid, but different, cell.
+        (Self::from(cell).as_ref() == bytes).then_some(cell)
+    }
 }
 
 impl From<CellIndex> for Key {
@@ -35,7 +64,35 @@ impl AsRef<[u8]> for Key {
     }
 }
 
+// Keys are ordered like their FST representation (i.e. lexicographically on
+// their meaningful bytes), not like the padded arrays.
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp(other.as_ref())
+    }
+}
+
 impl From<Key> for CellIndex {
+    fn from(value: Key) -> Self {
+        Self::try_from(u64::from(value)).expect("valid cell index")
+    }
+}
+
+impl From<Key> for u64 {
     #[expect(
         clippy::cast_possible_truncation,
         reason = "resolution is in [0; 15]"
@@ -47,12 +104,12 @@ impl From<Key> for CellIndex {
         // Default cell index (resolution 0, base cell 0).
         let mut index = 0x8001fffffffffff;
         // Resolution bit offset: 52.
-        index |= u64::from(res) << 52;
+        index |= Self::from(res) << 52;
         // Base cell bit offset: 45
-        index |= u64::from(key[0]) << 45;
+        index |= Self::from(key[0]) << 45;
 
         for (i, direction) in key[1..=usize::from(res)].iter().enumerate() {
-            let direction = u64::from(*direction);
+            let direction = Self::from(*direction);
             // +1 since we skip the first cell (base cell).
             let resolution = (i + 1) as u8;
             // Max res: 15, direction bit width: 3
@@ -60,7 +117,7 @@ impl From<Key> for CellIndex {
             index = (index & !(0b111 << offset)) | (direction << offset);
         }
 
-        Self::try_from(index).expect("valid cell index")
+        index
     }
 }
 
@@ -77,10 +134,61 @@ impl From<&[u8]> for Key {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_ord() {
+        let parent = Key::from(
+            CellIndex::try_from(0x85318d83fffffff).expect("valid cell"),
+        );
+        let child = Key::from(
+            CellIndex::try_from(0x86318d837ffffff).expect("valid cell"),
+        );
+        let sibling = Key::from(
+            CellIndex::try_from(0x85318d93fffffff).expect("valid cell"),
+        );
+
+        assert!(parent < child, "parent before its descendants");
+        assert!(child < sibling, "descendants before next sibling");
+    }
+
+    #[test]
+    fn test_descendant_bounds() {
+        // Regular cell, pentagon and resolution 14.
+        for value in [0x85318d83fffffff, 0x8009fffffffffff, 0x8e2a1072b598647] {
+            let index = CellIndex::try_from(value).expect("valid cell");
+            let resolution = index.resolution().succ().expect("finer res");
+            let first = index.children(resolution).next().expect("first child");
+            // Two levels down, or one for resolution 14.
+            let finest = resolution.succ().unwrap_or(resolution);
+            let last = index.children(finest).last().expect("last descendant");
+
+            let (start, end) =
+                Key::from(index).descendant_bounds().expect("bounds");
+            assert_eq!(CellIndex::from(start), first, "first of {index}");
+            assert!(Key::from(last) < end, "last of {index}");
+            assert!(Key::from(index) < start, "{index} excluded");
+        }
+
+        let index = CellIndex::try_from(0x8f2a1072b598641).expect("valid cell");
+        assert!(Key::from(index).descendant_bounds().is_none(), "res 15");
+    }
+
     #[test]
     fn test_from() {
         let index = CellIndex::try_from(0x8f2a1072b598641).expect("valid cell");
         let key = Key::from(index);
         assert_eq!(index, CellIndex::from(key));
     }
+
+    #[test]
+    fn test_try_decode() {
+        let index = CellIndex::try_from(0x8f2a1072b598641).expect("valid cell");
+        let key = Key::from(index);
+        assert_eq!(Key::try_decode(key.as_ref()), Some(index), "valid");
+
+        assert!(Key::try_decode(&[]).is_none(), "empty");
+        assert!(Key::try_decode(&[122]).is_none(), "invalid base cell");
+        assert!(Key::try_decode(&[21, 7]).is_none(), "invalid direction");
+        assert!(Key::try_decode(&[21, 0xff, 3]).is_none(), "padding");
+        assert!(Key::try_decode(&[21; 17]).is_none(), "too long");
+    }
 }
@@ -83,7 +83,6 @@
     clippy::string_add,
     clippy::string_lit_as_bytes,
     clippy::string_slice,
-    clippy::string_to_string,
     clippy::suboptimal_flops,
     clippy::suspicious_operation_groupings,
     clippy::todo,
@@ -119,16 +118,31 @@
 
 // }}}
 
+mod archive;
+mod columnar;
+mod compact;
 mod error;
 mod key;
 mod map;
+mod ops;
+mod packed;
 mod set;
+mod stats;
+mod value;
 
+pub use archive::{Archive, ArchiveBuilder};
+pub use columnar::ColumnarMap;
 pub use error::BuildError;
 pub use map::{
-    FrozenMap, FrozenMapBuilder, FrozenMapIterator, FrozenMapKeys,
-    FrozenMapValues,
+    FrozenMap, FrozenMapBuilder, FrozenMapCoarseningBuilder, FrozenMapIterator,
+    FrozenMapKeys, FrozenMapValues, SharedBytes,
 };
-pub use set::{FrozenSet, FrozenSetBuilder, FrozenSetIterator};
+pub use ops::{bin_points, diff, zip_map, DiffTag};
+pub use set::{
+    ContainsExplanation, FrozenSet, FrozenSetBuilder, FrozenSetIterator,
+    SingleResolutionSet,
+};
+pub use stats::{FstStats, Spread, ValueStats};
+pub use value::MapValue;
 
 use key::Key;
@@ -1,14 +1,16 @@
-use crate::{BuildError, Key};
+use crate::{BuildError, ColumnarMap, Key, MapValue, ValueStats};
 use either::Either;
 use fst::{
-    map::{Keys, Stream, Values},
+    map::{Keys, OpBuilder, Stream, Values},
     raw::Output,
     IntoStreamer, Map, MapBuilder, Streamer,
 };
-use h3o::CellIndex;
+use h3o::{CellIndex, LatLng, Resolution};
 use std::{
     io,
-    ops::{Bound, RangeBounds},
+    iter::FusedIterator,
+    ops::{Bound, Deref, RangeBounds},
+    sync::Arc,
 };
 
 /// A read-only map of H3 cell indexes.
@@ -43,8 +45,103 @@ impl<D: AsRef<[u8]>> FrozenMap<D> {
         Ok(Map::new(data).map(Self)?)
     }
 
+    /// Creates a map by merging several maps, given as raw byte sequences.
+    ///
+    /// When a cell index is present in more than one map, the associated
+    /// values are combined using `merge` (the order in which the values are
+    /// merged is unspecified).
+    ///
+    /// # Errors
+    ///
+    /// If one of the buffers doesn't contain a valid map, then an error is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let map1 = FrozenMap::try_from_iter(std::iter::once((index, 40)))?;
+    /// let map2 = FrozenMap::try_from_iter(std::iter::once((index, 2)))?;
+    ///
+    /// let map = FrozenMap::merge_from_bytes(
+    ///     &[map1.as_bytes(), map2.as_bytes()],
+    ///     |a, b| a + b,
+    /// )?;
+    /// assert_eq!(map.get(index), Some((index, 42)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[expect(
+        clippy::missing_panics_doc,
+        reason = "expect don't need to be documented"
+    )]
+    pub fn merge_from_bytes(
+        buffers: &[D],
+        merge: impl Fn(u64, u64) -> u64,
+    ) -> Result<FrozenMap<Vec<u8>>, BuildError> {
+        let maps = buffers
+            .iter()
+            .map(|data| Map::new(data.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut union = maps
+            .iter()
+            .fold(OpBuilder::new(), |mut op, map| {
+                op.push(map);
+                op
+            })
+            .union();
+
+        let mut builder = MapBuilder::memory();
+        while let Some((key, values)) = union.next() {
+            let value = values
+                .iter()
+                .map(|indexed| indexed.value)
+                .reduce(&merge)
+                .expect("at least one value per key");
+            builder.insert(key, value)?;
+        }
+
+        Ok(FrozenMap(builder.into_map()))
+    }
+
+    /// Returns a copy of this map that owns its data.
+    ///
+    /// This is useful to keep a map alive after the buffer it was loaded
+    /// from has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let map = FrozenMap::try_from_iter(std::iter::once((index, 42)))?;
+    /// let bytes = map.as_bytes().to_vec();
+    ///
+    /// let owned = FrozenMap::new(bytes.as_slice())?.to_owned();
+    /// drop(bytes);
+    ///
+    /// assert_eq!(owned.len(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[expect(
+        clippy::missing_panics_doc,
+        reason = "expect don't need to be documented"
+    )]
+    #[must_use]
+    pub fn to_owned(&self) -> FrozenMap<Vec<u8>> {
+        let bytes = self.0.as_fst().as_bytes().to_vec();
+        FrozenMap(Map::new(bytes).expect("valid map"))
+    }
+
     /// Returns the number of elements in this map.
     ///
+    /// This is a constant-time operation: the length is stored in the FST
+    /// metadata, read once at construction.
+    ///
     /// # Examples
     ///
     /// ```
@@ -162,13 +259,183 @@ impl<D: AsRef<[u8]>> FrozenMap<D> {
             if node.is_final() {
                 return Some((
                     Key::from(&key.as_ref()[..=i]).into(),
-                    output.value(),
+                    output.cat(node.final_output()).value(),
                 ));
             }
         }
         None
     }
 
+    /// Retrieves the value associated with a cell index (or one of its
+    /// ancestor), or `default` if there is none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let map = FrozenMap::try_from_iter(std::iter::once((cell, 42)))?;
+    ///
+    /// let child = CellIndex::try_from(0x8b1fb46622d8fff)?;
+    /// assert_eq!(map.get_or(child, 0), 42);
+    ///
+    /// let not_related = CellIndex::try_from(0x85283473fffffff)?;
+    /// assert_eq!(map.get_or(not_related, 0), 0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get_or(&self, index: CellIndex, default: u64) -> u64 {
+        self.get(index).map_or(default, |(_, value)| value)
+    }
+
+    /// Retrieves the payload associated with a cell index (or one of its
+    /// ancestor), using the value as an offset into `blob`.
+    ///
+    /// Payloads are expected to be `entry_len` bytes long (see
+    /// `FrozenMapBuilder::insert_with_offset`). If there is no value, or if
+    /// the payload is out of the blob bounds, then `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMapBuilder;
+    ///
+    /// let cell = CellIndex::try_from(0x85283473fffffff)?;
+    /// let mut blob = Vec::new();
+    /// let mut builder = FrozenMapBuilder::memory();
+    /// builder.insert_with_offset(cell, b"abcd", &mut blob)?;
+    /// let map = builder.into_map();
+    ///
+    /// assert_eq!(map.get_slice(cell, &blob, 4), Some(&b"abcd"[..]));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get_slice<'a>(
+        &self,
+        index: CellIndex,
+        blob: &'a [u8],
+        entry_len: usize,
+    ) -> Option<&'a [u8]> {
+        let (_, offset) = self.get(index)?;
+        let start = usize::try_from(offset).ok()?;
+        blob.get(start..start.checked_add(entry_len)?)
+    }
+
+    /// Retrieves every ancestor-or-self of a cell index present in the map,
+    /// along with its value, from the coarsest to the finest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let parent = cell.parent(Resolution::Five).expect("parent");
+    /// let map = FrozenMap::try_from_iter([(parent, 1), (cell, 2)])?;
+    ///
+    /// let child = CellIndex::try_from(0x8b1fb46622d8fff)?;
+    /// assert_eq!(map.get_path_values(child), vec![(parent, 1), (cell, 2)]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get_path_values(&self, index: CellIndex) -> Vec<(CellIndex, u64)> {
+        let fst = self.0.as_fst();
+        let key = Key::from(index);
+        let mut output = Output::zero();
+        let mut values = Vec::new();
+
+        let mut node = fst.root();
+        for (i, b) in key.as_ref().iter().enumerate() {
+            let Some(idx) = node.find_input(*b) else {
+                break;
+            };
+            let transition = node.transition(idx);
+            output = output.cat(transition.out);
+            node = fst.node(transition.addr);
+            if node.is_final() {
+                values.push((
+                    Key::from(&key.as_ref()[..=i]).into(),
+                    output.cat(node.final_output()).value(),
+                ));
+            }
+        }
+        values
+    }
+
+    /// Retrieves the value associated with a cell index, ignoring ancestors.
+    ///
+    /// Unlike `get`, only an exact match is considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let map = FrozenMap::try_from_iter(std::iter::once((cell, 42)))?;
+    ///
+    /// assert_eq!(map.get_exact(cell), Some(42));
+    ///
+    /// let child = CellIndex::try_from(0x8b1fb46622d8fff)?;
+    /// assert_eq!(map.get_exact(child), None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get_exact(&self, index: CellIndex) -> Option<u64> {
+        self.0.get(Key::from(index))
+    }
+
+    /// Retrieves the value associated with a cell index, ignoring ancestors,
+    /// or `default` if there is none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let map = FrozenMap::try_from_iter(std::iter::once((cell, 42)))?;
+    ///
+    /// assert_eq!(map.get_exact_or(cell, 0), 42);
+    ///
+    /// let child = CellIndex::try_from(0x8b1fb46622d8fff)?;
+    /// assert_eq!(map.get_exact_or(child, 0), 0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get_exact_or(&self, index: CellIndex, default: u64) -> u64 {
+        self.get_exact(index).unwrap_or(default)
+    }
+
+    /// Retrieves the value associated with a cell index (or one of its
+    /// ancestor), decoded as a `V`.
+    ///
+    /// The value must have been inserted with `FrozenMapBuilder::insert_value`,
+    /// using the same type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMapBuilder;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let mut builder = FrozenMapBuilder::memory();
+    /// builder.insert_value(cell, -1.5_f64)?;
+    /// let map = builder.into_map();
+    ///
+    /// assert_eq!(map.get_value::<f64>(cell), Some((cell, -1.5)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get_value<V: MapValue>(
+        &self,
+        index: CellIndex,
+    ) -> Option<(CellIndex, V)> {
+        self.get(index)
+            .map(|(cell, value)| (cell, V::from_u64(value)))
+    }
+
     /// Return a lexicographically ordered stream of every key-value (present
     /// in the map) that descend from the given cell index.
     ///
@@ -191,28 +458,130 @@ impl<D: AsRef<[u8]>> FrozenMap<D> {
     /// }
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    #[expect(
-        clippy::missing_panics_doc,
-        reason = "expect don't need to be documented"
-    )]
     pub fn descendants(
         &self,
         index: CellIndex,
-    ) -> impl Iterator<Item = (CellIndex, u64)> + '_ {
-        index.resolution().succ().map_or_else(
+    ) -> impl FusedIterator<Item = (CellIndex, u64)> + '_ {
+        Key::from(index).descendant_bounds().map_or_else(
             // If there is no lower resolution there can't be any descendants.
             || Either::Left(std::iter::empty()),
-            |resolution| {
-                let mut children = index.children(resolution);
-                let start = children.next().expect("first child");
-                let end = children.last().expect("last child");
-                Either::Right(
-                    self.range((Bound::Included(start), Bound::Included(end))),
-                )
+            |(start, end)| {
+                Either::Right(FrozenMapRangeIterator::new(
+                    self.0.range().ge(start).lt(end).into_stream(),
+                ))
             },
         )
     }
 
+    /// Retrieves the direct children of a cell index present in the map,
+    /// along with their values, in lexicographic order.
+    ///
+    /// Unlike `descendants`, deeper cells are never visited: each child is
+    /// looked up directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// assert_eq!(map.children_values(index).len(), 7);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn children_values(&self, parent: CellIndex) -> Vec<(CellIndex, u64)> {
+        parent
+            .resolution()
+            .succ()
+            .map_or_else(Vec::new, |resolution| {
+                parent
+                    .children(resolution)
+                    .filter_map(|cell| {
+                        self.get_exact(cell).map(|value| (cell, value))
+                    })
+                    .collect()
+            })
+    }
+
+    /// Returns the average of the values present within `k` rings of a cell
+    /// index, or `None` if there is none.
+    ///
+    /// Only exact matches are considered (see `get_exact`), and the cell
+    /// itself is included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let mut neighbors = cell.grid_disk::<Vec<_>>(1);
+    /// neighbors.retain(|&neighbor| neighbor != cell);
+    /// neighbors.sort_unstable();
+    /// let map =
+    ///     FrozenMap::try_from_iter([(neighbors[0], 1), (neighbors[1], 3)])?;
+    ///
+    /// assert_eq!(map.interpolate(cell, 1), Some(2.));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn interpolate(&self, index: CellIndex, k: u32) -> Option<f64> {
+        self.interpolate_weighted(index, k, |_| 1.)
+    }
+
+    /// Returns the weighted average of the values present within `k` rings
+    /// of a cell index, or `None` if there is none.
+    ///
+    /// Each value is weighted according to the grid distance between its cell
+    /// and `index` (0 for the cell itself, 1 for its direct neighbors, ...).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let mut disk = cell.grid_disk::<Vec<_>>(1);
+    /// disk.sort_unstable();
+    /// let map = FrozenMap::try_from_iter(disk.into_iter().map(|c| (c, 1)))?;
+    ///
+    /// let value = map.interpolate_weighted(cell, 1, |distance| {
+    ///     1. / f64::from(distance + 1)
+    /// });
+    /// assert_eq!(value, Some(1.));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "an approximation is good enough"
+    )]
+    pub fn interpolate_weighted(
+        &self,
+        index: CellIndex,
+        k: u32,
+        weight: impl Fn(u32) -> f64,
+    ) -> Option<f64> {
+        let (sum, total) = index
+            .grid_disk_distances::<Vec<_>>(k)
+            .into_iter()
+            .filter_map(|(cell, distance)| {
+                self.get_exact(cell).map(|value| (value, weight(distance)))
+            })
+            .fold((0., 0.), |acc, (value, weight)| {
+                ((value as f64).mul_add(weight, acc.0), acc.1 + weight)
+            });
+        (total > 0.).then(|| sum / total)
+    }
+
     /// Return a lexicographically ordered stream of all key-value pairs in this
     /// map.
     ///
@@ -239,7 +608,11 @@ impl<D: AsRef<[u8]>> FrozenMap<D> {
         FrozenMapIterator::new(self)
     }
 
-    /// Return a lexicographically ordered stream of all cells in this map.
+    /// Return a lexicographically ordered stream of the key-value pairs whose
+    /// value satisfies `pred`.
+    ///
+    /// The predicate is evaluated before decoding the key, thus rejected
+    /// entries don't pay for the `CellIndex` reconstruction.
     ///
     /// # Examples
     ///
@@ -255,17 +628,711 @@ impl<D: AsRef<[u8]>> FrozenMap<D> {
     ///         .map(|(idx, cell)| (cell, idx as u64)),
     /// )?;
     ///
-    /// for cell in map.keys() {
-    ///     println!("{cell}");
+    /// for (cell, value) in map.iter_filter(|value| value > 4) {
+    ///     println!("{cell} = {value}");
     /// }
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn keys(&self) -> FrozenMapKeys<'_> {
-        FrozenMapKeys::new(self)
+    pub fn iter_filter<'a>(
+        &'a self,
+        pred: impl Fn(u64) -> bool + 'a,
+    ) -> impl Iterator<Item = (CellIndex, u64)> + 'a {
+        let mut stream = self.0.stream();
+        std::iter::from_fn(move || {
+            while let Some((key, value)) = stream.next() {
+                if pred(value) {
+                    return Some((Key::from(key).into(), value));
+                }
+            }
+            None
+        })
     }
 
-    /// Return a stream of all values in this map ordered lexicographically by
-    /// each value's corresponding key.
+    /// Collects every key-value pair of this map, in lexicographic order, into
+    /// a vector allocated once and for all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// assert_eq!(map.to_vec().len(), 7);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_vec(&self) -> Vec<(CellIndex, u64)> {
+        let mut entries = Vec::with_capacity(self.len());
+        entries.extend(self.iter());
+        entries
+    }
+
+    /// Writes the entries of this map as JSON Lines.
+    ///
+    /// Entries are written in lexicographic order, one JSON object per line,
+    /// e.g. `{"cell":"8a1fb46622dffff","value":42}`, where the cell is the
+    /// canonical hexadecimal representation of the index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there was a problem writing to `w`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    /// use std::{fs, io};
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index.children(Resolution::Six).map(|cell| (cell, 42)),
+    /// )?;
+    ///
+    /// # let file_path = "";
+    /// let mut wtr = io::BufWriter::new(fs::File::create(file_path)?);
+    /// map.write_jsonl(&mut wtr)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_jsonl<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        for (cell, value) in self {
+            writeln!(w, r#"{{"cell":"{cell:x}","value":{value}}}"#)?;
+        }
+        Ok(())
+    }
+
+    /// Return a lexicographically ordered stream of all cells in this map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// for cell in map.keys() {
+    ///     println!("{cell}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn keys(&self) -> FrozenMapKeys<'_> {
+        FrozenMapKeys::new(self)
+    }
+
+    /// Return a lexicographically ordered stream of all key-value pairs in this
+    /// map, along with the boundary of each cell.
+    ///
+    /// Note that computing a cell boundary is relatively expensive, and a new
+    /// vector is allocated for every cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// for (cell, boundary, value) in map.iter_boundaries() {
+    ///     println!("{cell} = {value}: {boundary:?}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn iter_boundaries(
+        &self,
+    ) -> impl Iterator<Item = (CellIndex, Vec<LatLng>, u64)> + '_ {
+        self.iter().map(|(cell, value)| {
+            (cell, cell.boundary().iter().copied().collect(), value)
+        })
+    }
+
+    /// Return a lexicographically ordered stream of all cells in this map, as
+    /// raw 64-bit H3 indexes.
+    ///
+    /// The keys are decoded straight to `u64`, skipping the `CellIndex`
+    /// validation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// for cell in map.keys_u64() {
+    ///     println!("{cell:x}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn keys_u64(&self) -> impl Iterator<Item = u64> + '_ {
+        let mut keys = self.0.keys();
+        std::iter::from_fn(move || {
+            keys.next().map(|key| u64::from(Key::from(key)))
+        })
+    }
+
+    /// Return a stream of all values in this map ordered lexicographically by
+    /// each value's corresponding key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// for value in map.values() {
+    ///     println!("{value}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn values(&self) -> FrozenMapValues<'_> {
+        FrozenMapValues::new(self)
+    }
+
+    /// Collects every value of this map, ordered lexicographically by each
+    /// value's corresponding key, into a contiguous vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// assert_eq!(map.values_columnar(), vec![0, 1, 2, 3, 4, 5, 6]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn values_columnar(&self) -> Vec<u64> {
+        let mut values = Vec::with_capacity(self.len());
+        values.extend(self.values());
+        values
+    }
+
+    /// Create a `ColumnarMap` with the same content as this map.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new map cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index.children(Resolution::Six).map(|cell| (cell, 42)),
+    /// )?;
+    ///
+    /// let columnar = map.to_columnar()?;
+    /// assert_eq!(columnar.len(), 7);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_columnar(&self) -> Result<ColumnarMap, BuildError> {
+        ColumnarMap::new(self)
+    }
+
+    /// Computes the count, sum, min, max and mean of all the values in this
+    /// map, in a single pass.
+    ///
+    /// Returns `None` if the map is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// let stats = map.value_stats().expect("non-empty map");
+    /// assert_eq!(stats.max, 6);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn value_stats(&self) -> Option<ValueStats> {
+        ValueStats::new(self.values())
+    }
+
+    /// Counts the values of this map falling into each bucket defined by the
+    /// ascending `buckets` boundaries, in a single pass.
+    ///
+    /// Buckets are half-open: the first one holds the values below
+    /// `buckets[0]`, the i-th one the values in `buckets[i - 1]..buckets[i]`,
+    /// and the last one (overflow) the values greater than or equal to the
+    /// last boundary. Thus, `buckets.len() + 1` counts are returned.
+    ///
+    /// Returns `None` if the boundaries aren't sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// let histogram = map.value_histogram(&[2, 5]);
+    /// assert_eq!(histogram, Some(vec![2, 3, 2]));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn value_histogram(&self, buckets: &[u64]) -> Option<Vec<usize>> {
+        if buckets.windows(2).any(|pair| pair[0] > pair[1]) {
+            return None;
+        }
+
+        let mut counts = vec![0; buckets.len() + 1];
+        for value in self.values() {
+            counts[buckets.partition_point(|&boundary| boundary <= value)] += 1;
+        }
+        Some(counts)
+    }
+
+    /// Return a lexicographically ordered stream of key-value pairs in the
+    /// specified key range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    /// use std::ops::Bound;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// let start = Bound::Included(CellIndex::try_from(0x86318d817ffffff)?);
+    /// let end = Bound::Excluded(CellIndex::try_from(0x86318d827ffffff)?);
+    ///
+    /// for (cell, value) in map.range((start, end)) {
+    ///     println!("{cell} = {value}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn range(
+        &self,
+        range: impl RangeBounds<CellIndex>,
+    ) -> impl FusedIterator<Item = (CellIndex, u64)> + '_ {
+        if matches!(
+            (range.start_bound(), range.end_bound()),
+            (Bound::Unbounded, Bound::Unbounded)
+        ) {
+            return Either::Left(self.iter());
+        }
+        Either::Right(FrozenMapRangeIterator::new(self.range_stream(range)))
+    }
+
+    /// Return a lexicographically ordered stream of the key-value pairs
+    /// starting from the given cell index (included).
+    ///
+    /// This is a shorthand for an unbounded `range`, useful for paging.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Seven)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// // Fetch the next page, after the last seen cell.
+    /// let last = CellIndex::try_from(0x87318d800ffffff)?;
+    /// let page = map.iter_from(last).skip(1).take(10).collect::<Vec<_>>();
+    /// assert_eq!(page.len(), 10);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn iter_from(
+        &self,
+        start: CellIndex,
+    ) -> impl FusedIterator<Item = (CellIndex, u64)> + '_ {
+        self.range((Bound::Included(start), Bound::Unbounded))
+    }
+
+    /// Returns the minimum and maximum values of the key-value pairs in the
+    /// specified key range.
+    ///
+    /// If the range is empty, then `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    /// use std::ops::Bound;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// let start = Bound::Included(CellIndex::try_from(0x86318d817ffffff)?);
+    /// let end = Bound::Excluded(CellIndex::try_from(0x86318d827ffffff)?);
+    ///
+    /// assert_eq!(map.value_bounds_range((start, end)), Some((2, 3)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn value_bounds_range(
+        &self,
+        range: impl RangeBounds<CellIndex>,
+    ) -> Option<(u64, u64)> {
+        let mut stream = self.range_stream(range);
+        let mut bounds = None;
+
+        while let Some((_, value)) = stream.next() {
+            bounds = Some(bounds.map_or((value, value), |(min, max)| {
+                (u64::min(min, value), u64::max(max, value))
+            }));
+        }
+
+        bounds
+    }
+
+    /// Returns true if both maps have the same value for every key they
+    /// share.
+    ///
+    /// Keys present in only one of the maps are ignored, and only exact
+    /// matches are considered (i.e. a cell and its ancestor are different
+    /// keys).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let children = index.children(Resolution::Six).collect::<Vec<_>>();
+    /// let a = FrozenMap::try_from_iter(children.iter().map(|&c| (c, 1)))?;
+    /// let b =
+    ///     FrozenMap::try_from_iter(children.iter().take(3).map(|&c| (c, 1)))?;
+    ///
+    /// assert!(a.agrees_with(&b));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn agrees_with<D2: AsRef<[u8]>>(&self, other: &FrozenMap<D2>) -> bool {
+        let mut shared =
+            OpBuilder::new().add(&self.0).add(&other.0).intersection();
+        while let Some((_, values)) = shared.next() {
+            if values[0].value != values[1].value {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Return a lexicographically ordered stream of the keys whose value
+    /// differs between both maps, along with the value in each map.
+    ///
+    /// Keys present in only one of the maps are yielded as well, with `None`
+    /// on the other side. Like `agrees_with`, only exact matches are
+    /// considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let children = index.children(Resolution::Six).collect::<Vec<_>>();
+    /// let a = FrozenMap::try_from_iter(children.iter().map(|&c| (c, 1)))?;
+    /// let b =
+    ///     FrozenMap::try_from_iter(children.iter().take(3).map(|&c| (c, 1)))?;
+    ///
+    /// for (cell, left, right) in a.value_diff(&b) {
+    ///     println!("{cell}: {left:?} != {right:?}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn value_diff<'a, D2: AsRef<[u8]>>(
+        &'a self,
+        other: &'a FrozenMap<D2>,
+    ) -> impl Iterator<Item = (CellIndex, Option<u64>, Option<u64>)> + 'a {
+        let mut union = OpBuilder::new().add(&self.0).add(&other.0).union();
+        std::iter::from_fn(move || {
+            while let Some((key, values)) = union.next() {
+                let (mut left, mut right) = (None, None);
+                for value in values {
+                    if value.index == 0 {
+                        left = Some(value.value);
+                    } else {
+                        right = Some(value.value);
+                    }
+                }
+                if left != right {
+                    return Some((
+                        CellIndex::from(Key::from(key)),
+                        left,
+                        right,
+                    ));
+                }
+            }
+            None
+        })
+    }
+
+    /// Create a new map with the same keys, where each value is transformed
+    /// by `f`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new map cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let map = FrozenMap::try_from_iter(std::iter::once((index, 21)))?;
+    ///
+    /// let map = map.map_values(|value| value * 2)?;
+    /// assert_eq!(map.get(index), Some((index, 42)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn map_values(
+        &self,
+        mut f: impl FnMut(u64) -> u64,
+    ) -> Result<FrozenMap<Vec<u8>>, BuildError> {
+        let mut stream = self.0.stream();
+        let mut builder = MapBuilder::memory();
+
+        // Keys are unchanged, thus already ordered.
+        while let Some((key, value)) = stream.next() {
+            builder.insert(key, f(value))?;
+        }
+
+        FrozenMap::new(builder.into_inner()?)
+    }
+
+    /// Create a new map with the same keys, where each value is replaced by a
+    /// dense index in `0..n` (`n` being the number of distinct values).
+    ///
+    /// The lookup table, mapping each dense index back to the original value,
+    /// is returned alongside the new map. Indexes follow the ascending order
+    /// of the original values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new map cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index.children(Resolution::Six).map(|cell| (cell, 1000)),
+    /// )?;
+    ///
+    /// let (map, table) = map.dense_remap()?;
+    /// assert_eq!(table, vec![1000]);
+    /// assert!(map.values().all(|value| value == 0));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn dense_remap(
+        &self,
+    ) -> Result<(FrozenMap<Vec<u8>>, Vec<u64>), BuildError> {
+        let mut table = self.values().collect::<Vec<_>>();
+        table.sort_unstable();
+        table.dedup();
+
+        let map = self.map_values(|value| {
+            table.partition_point(|&distinct| distinct < value) as u64
+        })?;
+
+        Ok((map, table))
+    }
+
+    /// Create a new map where every cell index is replaced by its ancestor at
+    /// the given resolution.
+    ///
+    /// The values of cell indexes sharing the same ancestor are combined using
+    /// `merge` (e.g. to sum them). Cell indexes that are already coarser than
+    /// the target resolution are kept unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new map cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index.children(Resolution::Six).map(|cell| (cell, 1)),
+    /// )?;
+    ///
+    /// let map = map.rollup(Resolution::Five, |a, b| a + b)?;
+    /// assert_eq!(map.get(index), Some((index, 7)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn rollup(
+        &self,
+        resolution: Resolution,
+        merge: impl Fn(u64, u64) -> u64,
+    ) -> Result<FrozenMap<Vec<u8>>, BuildError> {
+        let mut rollup = Rollup::new(resolution);
+        let mut stream = self.0.stream();
+        while let Some((key, value)) = stream.next() {
+            rollup.push(key, value, &merge)?;
+        }
+        rollup.finish()
+    }
+
+    /// Create a new map where every cell index is replaced by its ancestor at
+    /// the given resolution, holding the maximum value of its descendants.
+    ///
+    /// This is a shorthand for `rollup` with `u64::max` as merge function.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new map cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// let map = map.downsample_max(Resolution::Five)?;
+    /// assert_eq!(map.get(index), Some((index, 6)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn downsample_max(
+        &self,
+        resolution: Resolution,
+    ) -> Result<FrozenMap<Vec<u8>>, BuildError> {
+        self.rollup(resolution, u64::max)
+    }
+
+    /// Create a new map where every cell index is replaced by its ancestor at
+    /// the given resolution, holding the minimum value of its descendants.
+    ///
+    /// This is a shorthand for `rollup` with `u64::min` as merge function.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new map cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let map = FrozenMap::try_from_iter(
+    ///     index
+    ///         .children(Resolution::Six)
+    ///         .enumerate()
+    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    /// )?;
+    ///
+    /// let map = map.downsample_min(Resolution::Five)?;
+    /// assert_eq!(map.get(index), Some((index, 0)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn downsample_min(
+        &self,
+        resolution: Resolution,
+    ) -> Result<FrozenMap<Vec<u8>>, BuildError> {
+        self.rollup(resolution, u64::min)
+    }
+
+    /// Create a new map where every cell index is replaced by its ancestor at
+    /// the given resolution, holding the sum of the values of its
+    /// descendants.
+    ///
+    /// This is a shorthand for `rollup` with a saturating addition as merge
+    /// function.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new map cannot be built.
     ///
     /// # Examples
     ///
@@ -281,62 +1348,77 @@ impl<D: AsRef<[u8]>> FrozenMap<D> {
     ///         .map(|(idx, cell)| (cell, idx as u64)),
     /// )?;
     ///
-    /// for value in map.values() {
-    ///     println!("{value}");
-    /// }
+    /// let map = map.downsample_sum(Resolution::Five)?;
+    /// assert_eq!(map.get(index), Some((index, 21)));
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn values(&self) -> FrozenMapValues<'_> {
-        FrozenMapValues::new(self)
+    pub fn downsample_sum(
+        &self,
+        resolution: Resolution,
+    ) -> Result<FrozenMap<Vec<u8>>, BuildError> {
+        self.rollup(resolution, u64::saturating_add)
     }
 
-    /// Return a lexicographically ordered stream of key-value pairs in the
-    /// specified key range.
+    /// Builds a `rollup` at each of the given resolutions, in a single pass
+    /// over the map.
+    ///
+    /// The maps are returned in the same order as `resolutions`, and each
+    /// level is computed from the values of this map (not from the previous
+    /// level).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one of the new maps cannot be built.
     ///
     /// # Examples
     ///
     /// ```
     /// use h3o::{CellIndex, Resolution};
     /// use h3o_ice::FrozenMap;
-    /// use std::ops::Bound;
     ///
     /// let index = CellIndex::try_from(0x85318d83fffffff)?;
     /// let map = FrozenMap::try_from_iter(
-    ///     index
-    ///         .children(Resolution::Six)
-    ///         .enumerate()
-    ///         .map(|(idx, cell)| (cell, idx as u64)),
+    ///     index.children(Resolution::Seven).map(|cell| (cell, 1)),
     /// )?;
     ///
-    /// let start = Bound::Included(CellIndex::try_from(0x86318d817ffffff)?);
-    /// let end = Bound::Excluded(CellIndex::try_from(0x86318d827ffffff)?);
-    ///
-    /// for (cell, value) in map.range((start, end)) {
-    ///     println!("{cell} = {value}");
-    /// }
+    /// let levels =
+    ///     map.pyramid(&[Resolution::Six, Resolution::Five], |a, b| a + b)?;
+    /// assert_eq!(levels[0].len(), 7);
+    /// assert_eq!(levels[1].get(index), Some((index, 49)));
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn range(
+    pub fn pyramid(
         &self,
-        range: impl RangeBounds<CellIndex>,
-    ) -> impl Iterator<Item = (CellIndex, u64)> + '_ {
-        let (start, end) = (range.start_bound(), range.end_bound());
-
-        if matches!((start, end), (Bound::Unbounded, Bound::Unbounded)) {
-            return Either::Left(self.iter());
+        resolutions: &[Resolution],
+        merge: impl Fn(u64, u64) -> u64,
+    ) -> Result<Vec<FrozenMap<Vec<u8>>>, BuildError> {
+        let mut levels = resolutions
+            .iter()
+            .map(|&resolution| Rollup::new(resolution))
+            .collect::<Vec<_>>();
+        let mut stream = self.0.stream();
+        while let Some((key, value)) = stream.next() {
+            for level in &mut levels {
+                level.push(key, value, &merge)?;
+            }
         }
+        levels.into_iter().map(Rollup::finish).collect()
+    }
+
+    /// Returns a raw stream over the key-value pairs in the specified range.
+    fn range_stream(&self, range: impl RangeBounds<CellIndex>) -> Stream<'_> {
         let builder = self.0.range();
-        let builder = match start {
+        let builder = match range.start_bound() {
             Bound::Included(lower) => builder.ge(Key::from(*lower)),
             Bound::Excluded(lower) => builder.gt(Key::from(*lower)),
             Bound::Unbounded => builder,
         };
-        let builder = match end {
+        let builder = match range.end_bound() {
             Bound::Included(upper) => builder.le(Key::from(*upper)),
             Bound::Excluded(upper) => builder.lt(Key::from(*upper)),
             Bound::Unbounded => builder,
         };
-        Either::Right(FrozenMapRangeIterator::new(builder.into_stream()))
+        builder.into_stream()
     }
 }
 
@@ -376,6 +1458,64 @@ impl FrozenMap<Vec<u8>> {
         Self::new(builder.into_inner()?)
     }
 
+    /// Create a `FrozenMap` by binning points into cells at the given
+    /// resolution.
+    ///
+    /// Points are converted to cells (in any order), and the values of points
+    /// falling into the same cell are combined using `merge`.
+    ///
+    /// # Errors
+    ///
+    /// This should never fail in practice, but the error from the underlying
+    /// builder is propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{LatLng, Resolution};
+    /// use h3o_ice::FrozenMap;
+    ///
+    /// // Count the points per cell.
+    /// let points = [
+    ///     LatLng::new(48.8566, 2.3522)?,
+    ///     LatLng::new(48.8567, 2.3523)?,
+    ///     LatLng::new(45.7640, 4.8357)?,
+    /// ];
+    /// let map = FrozenMap::try_from_latlng_values(
+    ///     points.into_iter().map(|point| (point, 1)),
+    ///     Resolution::Nine,
+    ///     |a, b| a + b,
+    /// )?;
+    /// assert_eq!(map.len(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_latlng_values(
+        items: impl IntoIterator<Item = (LatLng, u64)>,
+        resolution: Resolution,
+        merge: impl Fn(u64, u64) -> u64,
+    ) -> Result<Self, BuildError> {
+        let mut items = items
+            .into_iter()
+            .map(|(ll, value)| (Key::from(ll.to_cell(resolution)), value))
+            .collect::<Vec<_>>();
+        items.sort_unstable_by_key(|&(key, _)| key);
+
+        let mut builder = FrozenMapBuilder::memory();
+        let mut items = items.into_iter();
+        if let Some((mut key, mut value)) = items.next() {
+            for (next_key, next_value) in items {
+                if next_key == key {
+                    value = merge(value, next_value);
+                } else {
+                    builder.insert(key.into(), value)?;
+                    (key, value) = (next_key, next_value);
+                }
+            }
+            builder.insert(key.into(), value)?;
+        }
+        Self::new(builder.into_inner()?)
+    }
+
     /// Returns the binary contents of this map.
     /// # Examples
     ///
@@ -396,6 +1536,12 @@ impl FrozenMap<Vec<u8>> {
     }
 }
 
+impl Default for FrozenMap<Vec<u8>> {
+    fn default() -> Self {
+        FrozenMapBuilder::memory().into_map()
+    }
+}
+
 impl<'a, D: AsRef<[u8]>> IntoIterator for &'a FrozenMap<D> {
     type IntoIter = FrozenMapIterator<'a>;
     type Item = (CellIndex, u64);
@@ -480,6 +1626,106 @@ impl<W: io::Write> FrozenMapBuilder<W> {
         self.0.insert(Key::from(index), value).map_err(Into::into)
     }
 
+    /// Appends a payload to an external blob, and inserts the cell index
+    /// with the payload offset (in the blob) as value.
+    ///
+    /// Payloads can then be retrieved with `FrozenMap::get_slice`.
+    ///
+    /// # Errors
+    ///
+    /// If a cell index is inserted that is less than any previous cell index
+    /// added, then an error is returned. Similarly, if there was a problem
+    /// writing to the underlying writer, an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMapBuilder;
+    ///
+    /// let mut blob = Vec::new();
+    /// let mut builder = FrozenMapBuilder::memory();
+    /// let cell = CellIndex::try_from(0x85283473fffffff)?;
+    /// builder.insert_with_offset(cell, b"abcd", &mut blob)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn insert_with_offset(
+        &mut self,
+        index: CellIndex,
+        payload: &[u8],
+        blob: &mut Vec<u8>,
+    ) -> Result<(), BuildError> {
+        self.insert(index, blob.len() as u64)?;
+        blob.extend_from_slice(payload);
+        Ok(())
+    }
+
+    /// Insert a new key-value pair into the map, encoding the value as a
+    /// `u64` (see `MapValue`).
+    ///
+    /// # Errors
+    ///
+    /// If a cell index is inserted that is less than any previous cell index
+    /// added, then an error is returned. Similarly, if there was a problem
+    /// writing to the underlying writer, an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMapBuilder;
+    ///
+    /// let mut builder = FrozenMapBuilder::memory();
+    /// builder.insert_value(CellIndex::try_from(0x85283473fffffff)?, -7_i32)?;
+    /// builder.insert_value(CellIndex::try_from(0x85318d83fffffff)?, 42_i32)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn insert_value<V: MapValue>(
+        &mut self,
+        index: CellIndex,
+        value: V,
+    ) -> Result<(), BuildError> {
+        self.insert(index, value.to_u64())
+    }
+
+    /// Insert a new key-value pair into the map, unless the value is zero.
+    ///
+    /// This is useful to build sparse maps (e.g. counts), where zero values
+    /// would waste space. Note that this is lossy: `get` returns `None` for
+    /// the skipped cell indexes (or the value of one of their ancestors), and
+    /// it's up to the caller to interpret the absence of value as a zero.
+    ///
+    /// # Errors
+    ///
+    /// If a cell index is inserted that is less than any previous cell index
+    /// added, then an error is returned. Similarly, if there was a problem
+    /// writing to the underlying writer, an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenMapBuilder;
+    ///
+    /// let mut builder = FrozenMapBuilder::memory();
+    /// builder.insert_nonzero(CellIndex::try_from(0x85283473fffffff)?, 0)?;
+    /// builder.insert_nonzero(CellIndex::try_from(0x85318d83fffffff)?, 42)?;
+    ///
+    /// let map = builder.into_map();
+    /// assert_eq!(map.len(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn insert_nonzero(
+        &mut self,
+        index: CellIndex,
+        value: u64,
+    ) -> Result<(), BuildError> {
+        if value == 0 {
+            return Ok(());
+        }
+        self.insert(index, value)
+    }
+
     /// Calls insert on each cell index in the iterator.
     ///
     /// If an error occurred while adding an element, processing is stopped
@@ -523,6 +1769,44 @@ impl<W: io::Write> FrozenMapBuilder<W> {
     pub fn into_inner(self) -> Result<W, BuildError> {
         self.0.into_inner().map_err(Into::into)
     }
+
+    /// Turns this builder into one that maps every inserted cell index finer
+    /// than `resolution` to its ancestor at `resolution`.
+    ///
+    /// Values of cell indexes mapped to the same ancestor are combined using
+    /// `merge`, which allows to build a coarse map from fine input in a single
+    /// pass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMapBuilder;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let mut builder =
+    ///     FrozenMapBuilder::memory().coarsen_to(Resolution::Five, |a, b| a + b);
+    /// builder.extend_iter(index.children(Resolution::Seven).map(|c| (c, 1)))?;
+    ///
+    /// let map = builder.into_map()?;
+    /// assert_eq!(map.get(index), Some((index, 49)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub const fn coarsen_to<F>(
+        self,
+        resolution: Resolution,
+        merge: F,
+    ) -> FrozenMapCoarseningBuilder<W, F>
+    where
+        F: FnMut(u64, u64) -> u64,
+    {
+        FrozenMapCoarseningBuilder {
+            builder: self,
+            resolution,
+            merge,
+            pending: None,
+        }
+    }
 }
 
 impl FrozenMapBuilder<Vec<u8>> {
@@ -539,6 +1823,177 @@ impl FrozenMapBuilder<Vec<u8>> {
     pub fn into_map(self) -> FrozenMap<Vec<u8>> {
         FrozenMap(self.0.into_map())
     }
+
+    /// Finishes the construction of the map and returns it, along with its
+    /// binary contents.
+    ///
+    /// Both share the same buffer, so the bytes can be persisted while the map
+    /// is queried, without copying the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenMapBuilder;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let mut builder = FrozenMapBuilder::memory();
+    /// builder.insert(index, 42)?;
+    ///
+    /// let (map, bytes) = builder.into_map_and_bytes();
+    /// assert_eq!(map.get(index), Some((index, 42)));
+    /// assert!(!bytes.is_empty());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[expect(
+        clippy::missing_panics_doc,
+        reason = "expect don't need to be documented"
+    )]
+    #[must_use]
+    pub fn into_map_and_bytes(self) -> (FrozenMap<SharedBytes>, SharedBytes) {
+        let bytes = SharedBytes(Arc::new(
+            self.0.into_inner().expect("in-memory writer"),
+        ));
+        let map = FrozenMap::new(bytes.clone()).expect("valid map");
+        (map, bytes)
+    }
+}
+
+// ------------------------------------------------------------------------------
+
+/// A read-only byte buffer, shared without copying.
+///
+/// Created by `FrozenMapBuilder::into_map_and_bytes`.
+#[derive(Debug, Clone)]
+#[expect(
+    clippy::rc_buffer,
+    reason = "converting the builder's Vec into an Arc<[u8]> copies it"
+)]
+pub struct SharedBytes(Arc<Vec<u8>>);
+
+impl AsRef<[u8]> for SharedBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Deref for SharedBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+// ------------------------------------------------------------------------------
+
+/// A builder for creating a frozen map at a coarser resolution than its input.
+///
+/// Created by `FrozenMapBuilder::coarsen_to`.
+pub struct FrozenMapCoarseningBuilder<W, F> {
+    builder: FrozenMapBuilder<W>,
+    resolution: Resolution,
+    merge: F,
+    // Since ancestors of ordered cell indexes are ordered as well, only the
+    // last ancestor can still be merged.
+    pending: Option<(CellIndex, u64)>,
+}
+
+impl<W, F> FrozenMapCoarseningBuilder<W, F>
+where
+    W: io::Write,
+    F: FnMut(u64, u64) -> u64,
+{
+    /// Insert a new key-value pair into the map.
+    ///
+    /// If the cell index is finer than the target resolution, its ancestor at
+    /// that resolution is inserted instead.
+    ///
+    /// # Errors
+    ///
+    /// If a cell index is inserted that is less than any previous cell index
+    /// added, then an error is returned. Similarly, if there was a problem
+    /// writing to the underlying writer, an error is returned.
+    pub fn insert(
+        &mut self,
+        index: CellIndex,
+        value: u64,
+    ) -> Result<(), BuildError> {
+        let index = index.parent(self.resolution).unwrap_or(index);
+        match self.pending {
+            Some((pending, ref mut acc)) if pending == index => {
+                *acc = (self.merge)(*acc, value);
+            }
+            _ => {
+                if let Some((pending, acc)) =
+                    self.pending.replace((index, value))
+                {
+                    self.builder.insert(pending, acc)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Calls insert on each cell index in the iterator.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurred while adding an element, processing is stopped
+    /// and the error is returned.
+    pub fn extend_iter(
+        &mut self,
+        iter: impl IntoIterator<Item = (CellIndex, u64)>,
+    ) -> Result<(), BuildError> {
+        for (index, value) in iter {
+            self.insert(index, value)?;
+        }
+        Ok(())
+    }
+
+    /// Finishes the construction of the map and flushes the underlying
+    /// writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there was a problem writing to the underlying
+    /// writer.
+    pub fn finish(self) -> Result<(), BuildError> {
+        self.flush()?.finish()
+    }
+
+    /// Just like `finish`, except it returns the underlying writer after
+    /// flushing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there was a problem writing to the underlying
+    /// writer.
+    pub fn into_inner(self) -> Result<W, BuildError> {
+        self.flush()?.into_inner()
+    }
+
+    /// Inserts the pending key-value pair, if any.
+    fn flush(mut self) -> Result<FrozenMapBuilder<W>, BuildError> {
+        if let Some((index, value)) = self.pending.take() {
+            self.builder.insert(index, value)?;
+        }
+        Ok(self.builder)
+    }
+}
+
+impl<F> FrozenMapCoarseningBuilder<Vec<u8>, F>
+where
+    F: FnMut(u64, u64) -> u64,
+{
+    /// Finishes the construction of the map and returns it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the last key-value pair cannot be inserted.
+    pub fn into_map(self) -> Result<FrozenMap<Vec<u8>>, BuildError> {
+        Ok(self.flush()?.into_map())
+    }
 }
 
 // ------------------------------------------------------------------------------
@@ -578,6 +2033,8 @@ impl Iterator for FrozenMapIterator<'_> {
     }
 }
 
+impl FusedIterator for FrozenMapIterator<'_> {}
+
 impl ExactSizeIterator for FrozenMapIterator<'_> {
     // We can easily calculate the remaining number of iterations.
     fn len(&self) -> usize {
@@ -622,6 +2079,8 @@ impl Iterator for FrozenMapKeys<'_> {
     }
 }
 
+impl FusedIterator for FrozenMapKeys<'_> {}
+
 impl ExactSizeIterator for FrozenMapKeys<'_> {
     // We can easily calculate the remaining number of iterations.
     fn len(&self) -> usize {
@@ -665,6 +2124,8 @@ impl Iterator for FrozenMapValues<'_> {
     }
 }
 
+impl FusedIterator for FrozenMapValues<'_> {}
+
 impl ExactSizeIterator for FrozenMapValues<'_> {
     // We can easily calculate the remaining number of iterations.
     fn len(&self) -> usize {
@@ -694,3 +2155,57 @@ impl Iterator for FrozenMapRangeIterator<'_> {
             .map(|(key, value)| (Key::from(key).into(), value))
     }
 }
+
+impl FusedIterator for FrozenMapRangeIterator<'_> {}
+
+// ------------------------------------------------------------------------------
+
+/// Combines the values of an ordered stream of keys into their ancestor at a
+/// given resolution.
+struct Rollup {
+    /// Key length at the target resolution.
+    len: usize,
+    builder: MapBuilder<Vec<u8>>,
+    /// Ancestor being accumulated, along with its value.
+    pending: Option<(Vec<u8>, u64)>,
+}
+
+impl Rollup {
+    fn new(resolution: Resolution) -> Self {
+        Self {
+            // Base cell + one per resolution.
+            len: usize::from(u8::from(resolution)) + 1,
+            builder: MapBuilder::memory(),
+            pending: None,
+        }
+    }
+
+    fn push(
+        &mut self,
+        key: &[u8],
+        value: u64,
+        merge: impl Fn(u64, u64) -> u64,
+    ) -> Result<(), BuildError> {
+        // Ancestors of ordered keys are ordered too, thus the ones sharing the
+        // same ancestor are contiguous.
+        let key = &key[..key.len().min(self.len)];
+        self.pending = match self.pending.take() {
+            Some((ancestor, acc)) if ancestor == key => {
+                Some((ancestor, merge(acc, value)))
+            }
+            Some((ancestor, acc)) => {
+                self.builder.insert(ancestor, acc)?;
+                Some((key.to_vec(), value))
+            }
+            None => Some((key.to_vec(), value)),
+        };
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<FrozenMap<Vec<u8>>, BuildError> {
+        if let Some((ancestor, acc)) = self.pending {
+            self.builder.insert(ancestor, acc)?;
+        }
+        FrozenMap::new(self.builder.into_inner()?)
+    }
+}
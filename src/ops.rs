@@ -0,0 +1,139 @@
+use crate::{BuildError, FrozenMap, FrozenSet, Key};
+use h3o::{CellIndex, LatLng, Resolution};
+use std::cmp::Ordering;
+
+/// Tag describing where a cell is present, as returned by `diff`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DiffTag {
+    /// Cell only present in the left set.
+    OnlyLeft,
+    /// Cell only present in the right set.
+    OnlyRight,
+    /// Cell present in both sets.
+    Both,
+}
+
+/// Return a lexicographically ordered stream of the cells of a set, along
+/// with their value in the given map (if any).
+///
+/// Only exact matches are considered when looking up the values.
+///
+/// # Examples
+///
+/// ```
+/// use h3o::{CellIndex, Resolution};
+/// use h3o_ice::{FrozenMap, FrozenSet};
+///
+/// let index = CellIndex::try_from(0x85318d83fffffff)?;
+/// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+/// let map = FrozenMap::try_from_iter(
+///     index.children(Resolution::Six).take(2).map(|cell| (cell, 42)),
+/// )?;
+///
+/// for (cell, value) in h3o_ice::zip_map(&set, &map) {
+///     println!("{cell} = {value:?}");
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn zip_map<'a, D1, D2>(
+    set: &'a FrozenSet<D1>,
+    map: &'a FrozenMap<D2>,
+) -> impl Iterator<Item = (CellIndex, Option<u64>)> + 'a
+where
+    D1: AsRef<[u8]>,
+    D2: AsRef<[u8]>,
+{
+    set.iter().map(|cell| (cell, map.get_exact(cell)))
+}
+
+/// Return a lexicographically ordered stream of the cells of two sets, tagged
+/// according to the set(s) they belong to.
+///
+/// The sets are merged in a single pass and each cell is yielded once. Note
+/// that only exact matches are considered (i.e. a cell and its ancestor are
+/// different cells).
+///
+/// # Examples
+///
+/// ```
+/// use h3o::{CellIndex, Resolution};
+/// use h3o_ice::{DiffTag, FrozenSet};
+///
+/// let index = CellIndex::try_from(0x85318d83fffffff)?;
+/// let a = FrozenSet::try_from_iter(index.children(Resolution::Six).take(4))?;
+/// let b = FrozenSet::try_from_iter(index.children(Resolution::Six).skip(2))?;
+///
+/// for (cell, tag) in h3o_ice::diff(&a, &b) {
+///     if tag != DiffTag::Both {
+///         println!("{cell} changed");
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn diff<'a, D1, D2>(
+    a: &'a FrozenSet<D1>,
+    b: &'a FrozenSet<D2>,
+) -> impl Iterator<Item = (CellIndex, DiffTag)> + 'a
+where
+    D1: AsRef<[u8]>,
+    D2: AsRef<[u8]>,
+{
+    let mut left = a.iter().peekable();
+    let mut right = b.iter().peekable();
+
+    std::iter::from_fn(move || {
+        let ordering = match (left.peek(), right.peek()) {
+            (Some(lhs), Some(rhs)) => Key::from(*lhs).cmp(&Key::from(*rhs)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => return None,
+        };
+        match ordering {
+            Ordering::Less => left.next().map(|cell| (cell, DiffTag::OnlyLeft)),
+            Ordering::Greater => {
+                right.next().map(|cell| (cell, DiffTag::OnlyRight))
+            }
+            Ordering::Equal => {
+                right.next();
+                left.next().map(|cell| (cell, DiffTag::Both))
+            }
+        }
+    })
+}
+
+/// Bins points into cells at the given resolution, counting the points per
+/// cell.
+///
+/// Points can be given in any order.
+///
+/// # Errors
+///
+/// This should never fail in practice, but the error from the underlying
+/// builder is propagated.
+///
+/// # Examples
+///
+/// ```
+/// use h3o::{LatLng, Resolution};
+///
+/// let points = [
+///     LatLng::new(48.8566, 2.3522)?,
+///     LatLng::new(48.8567, 2.3523)?,
+///     LatLng::new(45.7640, 4.8357)?,
+/// ];
+/// let counts = h3o_ice::bin_points(points, Resolution::Nine)?;
+///
+/// assert_eq!(counts.values().sum::<u64>(), 3);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn bin_points(
+    points: impl IntoIterator<Item = LatLng>,
+    resolution: Resolution,
+) -> Result<FrozenMap<Vec<u8>>, BuildError> {
+    FrozenMap::try_from_latlng_values(
+        points.into_iter().map(|point| (point, 1)),
+        resolution,
+        u64::saturating_add,
+    )
+}
@@ -0,0 +1,71 @@
+/// Encodes a list of integers as varint-encoded deltas.
+///
+/// The input must be sorted in ascending order.
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "only the low 7 bits are kept"
+)]
+pub fn encode(values: &[u64]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(values.len() * 2);
+    let mut previous = 0;
+    for &value in values {
+        let mut delta = value - previous;
+        // LEB128: 7 bits per byte, high bit set when more bytes follow.
+        while delta >= 0x80 {
+            bytes.push((delta & 0x7f) as u8 | 0x80);
+            delta >>= 7;
+        }
+        bytes.push(delta as u8);
+        previous = value;
+    }
+    bytes
+}
+
+/// Decodes a list of integers encoded by `encode`.
+///
+/// Returns `None` if the input is truncated or overflows.
+pub fn decode(mut bytes: &[u8]) -> Option<Vec<u64>> {
+    let mut values = Vec::new();
+    let mut previous = 0_u64;
+    while !bytes.is_empty() {
+        let mut delta = 0_u64;
+        let mut shift = 0;
+        loop {
+            let (&byte, rest) = bytes.split_first()?;
+            bytes = rest;
+            let chunk = u64::from(byte & 0x7f);
+            if shift > 63 || (chunk << shift) >> shift != chunk {
+                return None;
+            }
+            delta |= chunk << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        previous = previous.checked_add(delta)?;
+        values.push(previous);
+    }
+    Some(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let values = [0, 1, 127, 128, 0x85318d83fffffff, u64::MAX];
+        let bytes = encode(&values);
+        assert_eq!(decode(&bytes), Some(values.to_vec()));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(decode(&[0x80]).is_none(), "truncated");
+        assert!(decode(&[0xff; 11]).is_none(), "too long");
+        let mut bytes = encode(&[u64::MAX]);
+        bytes.push(1);
+        assert!(decode(&bytes).is_none(), "overflow");
+    }
+}
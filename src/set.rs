@@ -1,13 +1,22 @@
-use crate::{BuildError, Key};
+use crate::{
+    compact, packed, stats::ResolutionRange, BuildError, FrozenMap,
+    FrozenMapBuilder, FstStats, Key, Spread,
+};
 use either::Either;
 use fst::{set::Stream, IntoStreamer, Set, SetBuilder, Streamer};
-use h3o::CellIndex;
+use h3o::{CellIndex, LatLng, Resolution};
 use std::{
+    cmp::Ordering,
+    collections::HashMap,
     io,
-    ops::{Bound, RangeBounds},
+    iter::FusedIterator,
+    num::NonZeroUsize,
+    ops::{Bound, ControlFlow, RangeBounds},
+    sync::Arc,
 };
 
 /// A read-only set of H3 cell indexes.
+#[derive(Clone)]
 pub struct FrozenSet<D>(Set<D>);
 
 impl<D: AsRef<[u8]>> FrozenSet<D> {
@@ -39,8 +48,115 @@ impl<D: AsRef<[u8]>> FrozenSet<D> {
         Ok(Set::new(data).map(Self)?)
     }
 
+    /// Creates a set from its representation as a raw byte sequence, after
+    /// having verified its integrity.
+    ///
+    /// Unlike `new`, the whole data is read to compute its checksum, which
+    /// allows to detect corrupted data upfront. The checksum is the one built
+    /// into the FST format: the bytes from `as_bytes` already include it.
+    ///
+    /// # Errors
+    ///
+    /// If the format is invalid, then an error is returned.
+    ///
+    /// If the computed checksum doesn't match the expected one, then
+    /// `BuildError::ChecksumMismatch` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use h3o_ice::FrozenSet;
+    /// use std::fs;
+    ///
+    /// # let file_path = "";
+    /// let bytes = fs::read(file_path)?;
+    /// let set = FrozenSet::read_verified(bytes)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn read_verified(data: D) -> Result<Self, BuildError> {
+        let set = Self::new(data)?;
+        set.0
+            .as_fst()
+            .verify()
+            .map_err(|_| BuildError::ChecksumMismatch)?;
+        Ok(set)
+    }
+
+    /// Creates a set of cells at the same resolution from its representation
+    /// as a raw byte sequence.
+    ///
+    /// Such a set offers a faster `contains`, at the cost of reading the whole
+    /// set upfront to check its resolutions.
+    ///
+    /// # Errors
+    ///
+    /// If the format is invalid, then an error is returned.
+    ///
+    /// If the set contains cells at different resolutions, then
+    /// `BuildError::MixedResolutions` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// let set = FrozenSet::new_single_resolution(set.as_bytes())?;
+    /// assert_eq!(set.resolution(), Some(Resolution::Six));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn new_single_resolution(
+        data: D,
+    ) -> Result<SingleResolutionSet<D>, BuildError> {
+        let set = Self::new(data)?;
+        let resolution = match set.resolution_range() {
+            Some((min, max)) if min != max => {
+                return Err(BuildError::MixedResolutions)
+            }
+            range => range.map(|(min, _)| min),
+        };
+        Ok(SingleResolutionSet { set, resolution })
+    }
+
+    /// Returns a copy of this set that owns its data.
+    ///
+    /// This is useful to keep a set alive after the buffer it was loaded
+    /// from has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(index))?;
+    /// let bytes = set.as_bytes().to_vec();
+    ///
+    /// let owned = FrozenSet::new(bytes.as_slice())?.to_owned();
+    /// drop(bytes);
+    ///
+    /// assert_eq!(owned.len(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[expect(
+        clippy::missing_panics_doc,
+        reason = "expect don't need to be documented"
+    )]
+    #[must_use]
+    pub fn to_owned(&self) -> FrozenSet<Vec<u8>> {
+        let bytes = self.0.as_fst().as_bytes().to_vec();
+        FrozenSet(Set::new(bytes).expect("valid set"))
+    }
+
     /// Returns the number of elements in this set.
     ///
+    /// This is a constant-time operation: the length is stored in the FST
+    /// metadata, read once at construction.
+    ///
     /// # Examples
     ///
     /// ```
@@ -78,6 +194,116 @@ impl<D: AsRef<[u8]>> FrozenSet<D> {
         self.0.is_empty()
     }
 
+    /// Returns the average number of bytes used per cell, or 0 for an empty
+    /// set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Seven))?;
+    ///
+    /// println!("{:.2} bytes per cell", set.bytes_per_cell());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "an approximation is good enough"
+    )]
+    #[must_use]
+    pub fn bytes_per_cell(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        self.fst_bytes().len() as f64 / self.len() as f64
+    }
+
+    /// Returns statistics about the underlying FST.
+    ///
+    /// Note that counting the nodes requires to walk the whole FST.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(index))?;
+    /// let stats = set.fst_stats();
+    ///
+    /// assert_eq!(stats.len, 1);
+    /// assert!(stats.nodes > 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn fst_stats(&self) -> FstStats {
+        FstStats::new(self.0.as_fst())
+    }
+
+    /// Returns a summary of how globally spread this set is: the number of
+    /// distinct base cells it touches, and its range of resolutions.
+    ///
+    /// Returns `None` if the set is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    /// let spread = set.spread().expect("non-empty set");
+    ///
+    /// assert_eq!(spread.base_cells, 1);
+    /// assert_eq!(spread.min_res, Resolution::Six);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn spread(&self) -> Option<Spread> {
+        Spread::new(&self.0)
+    }
+
+    /// Returns the finest common ancestor of the cells of the set, i.e. the
+    /// finest cell whose subtree contains every cell of the set.
+    ///
+    /// Returns `None` if the set is empty or spans several base cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Seven))?;
+    ///
+    /// assert_eq!(set.bounding_cell(), Some(index));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn bounding_cell(&self) -> Option<CellIndex> {
+        let mut stream = self.0.stream();
+        let mut prefix = stream.next()?.to_vec();
+        while let Some(key) = stream.next() {
+            let len = prefix
+                .iter()
+                .zip(key)
+                .take_while(|&(lhs, rhs)| lhs == rhs)
+                .count();
+            // No common base cell, no need to go further.
+            if len == 0 {
+                return None;
+            }
+            prefix.truncate(len);
+        }
+        Key::try_decode(&prefix)
+    }
+
     /// Tests the membership of a single H3 cell index.
     ///
     /// Returns true if the cell index or one of its ancestor is present.
@@ -104,22 +330,110 @@ impl<D: AsRef<[u8]>> FrozenSet<D> {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn contains(&self, index: CellIndex) -> Option<CellIndex> {
+        self.contains_depth(index).map(|(cell, _)| cell)
+    }
+
+    /// Tests the membership of a single raw 64-bit H3 cell index.
+    ///
+    /// This is the same as `contains`, but takes and returns raw indexes (as
+    /// used by the H3 C API). Invalid indexes are never present: `None` is
+    /// returned instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(cell))?;
+    ///
+    /// let child = 0x8b1fb46622d8fff;
+    /// assert_eq!(set.contains_raw(child), Some(0x8a1fb46622dffff));
+    /// assert_eq!(set.contains_raw(0xdeadbeef), None);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn contains_raw(&self, index: u64) -> Option<u64> {
+        let index = CellIndex::try_from(index).ok()?;
+        self.contains(index).map(u64::from)
+    }
+
+    /// Tests the membership of a single H3 cell index, and returns the
+    /// resolution at which it matched.
+    ///
+    /// This is the same as `contains`, but also returns the resolution of the
+    /// matching cell (the coarser, the less precise the match).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(cell))?;
+    ///
+    /// let descendant = CellIndex::try_from(0x8d1fb46622d85bf)?;
+    /// assert_eq!(
+    ///     set.contains_depth(descendant),
+    ///     Some((cell, Resolution::Ten))
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn contains_depth(
+        &self,
+        index: CellIndex,
+    ) -> Option<(CellIndex, Resolution)> {
+        self.contains_traced(index)
+            .0
+            .map(|cell| (cell, cell.resolution()))
+    }
+
+    /// Tests the membership of a single H3 cell index, and returns the number
+    /// of transitions walked in the underlying FST.
+    ///
+    /// This is the same as `contains`, but also reports how deep the lookup
+    /// went (whether it matched or not), which is useful for profiling.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(cell))?;
+    ///
+    /// let descendant = CellIndex::try_from(0x8d1fb46622d85bf)?;
+    /// assert_eq!(set.contains_traced(descendant), (Some(cell), 11));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn contains_traced(
+        &self,
+        index: CellIndex,
+    ) -> (Option<CellIndex>, usize) {
         let fst = self.0.as_fst();
         let key = Key::from(index);
 
         let mut node = fst.root();
         for (i, b) in key.as_ref().iter().enumerate() {
-            let idx = node.find_input(*b)?;
+            let Some(idx) = node.find_input(*b) else {
+                return (None, i);
+            };
             node = fst.node(node.transition_addr(idx));
             if node.is_final() {
-                return Some(Key::from(&key.as_ref()[..=i]).into());
+                return (Some(Key::from(&key.as_ref()[..=i]).into()), i + 1);
             }
         }
-        None
+        (None, key.as_ref().len())
     }
 
-    /// Return a lexicographically ordered stream of every descendant (present
-    /// in the set) of the given cell index.
+    /// Tests the membership of a single H3 cell index, and returns the finest
+    /// matching cell of the set.
+    ///
+    /// Unlike `contains`, which stops at the first (i.e. coarsest) ancestor
+    /// present in the set, the whole key is walked: if the set holds several
+    /// ancestors of the cell index, the finest one is returned.
     ///
     /// # Examples
     ///
@@ -127,159 +441,2208 @@ impl<D: AsRef<[u8]>> FrozenSet<D> {
     /// use h3o::{CellIndex, Resolution};
     /// use h3o_ice::FrozenSet;
     ///
-    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
-    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let child = CellIndex::try_from(0x8b1fb46622d8fff)?;
+    /// let set = FrozenSet::try_from_iter([cell, child])?;
+    ///
+    /// let descendant = CellIndex::try_from(0x8d1fb46622d85bf)?;
+    /// assert_eq!(set.contains(descendant), Some(cell));
+    /// assert_eq!(set.contains_finest(descendant), Some(child));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn contains_finest(&self, index: CellIndex) -> Option<CellIndex> {
+        let fst = self.0.as_fst();
+        let key = Key::from(index);
+
+        let mut node = fst.root();
+        let mut finest = None;
+        for (i, b) in key.as_ref().iter().enumerate() {
+            let Some(idx) = node.find_input(*b) else {
+                break;
+            };
+            node = fst.node(node.transition_addr(idx));
+            if node.is_final() {
+                finest = Some(i);
+            }
+        }
+        finest.map(|i| Key::from(&key.as_ref()[..=i]).into())
+    }
+
+    /// Tests the membership of a single H3 cell index, and reports every step
+    /// of the lookup in the underlying FST.
+    ///
+    /// Unlike `contains_traced`, the walk doesn't stop at the first match:
+    /// this is useful to understand why a cell index didn't match (e.g.
+    /// where the lookup diverged from the expected cells).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(cell))?;
+    ///
+    /// let sibling = CellIndex::try_from(0x8a1fb46622d7fff)?;
+    /// let explanation = set.explain_contains(sibling);
+    /// assert_eq!(explanation.steps[10], (Resolution::Ten, false, false));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn explain_contains(&self, index: CellIndex) -> ContainsExplanation {
+        let fst = self.0.as_fst();
+        let key = Key::from(index);
+
+        let mut node = Some(fst.root());
+        let steps = Resolution::range(Resolution::Zero, index.resolution())
+            .zip(key.as_ref())
+            .map(|(resolution, b)| {
+                node = node
+                    .and_then(|node| {
+                        node.find_input(*b).map(|idx| node.transition_addr(idx))
+                    })
+                    .map(|addr| fst.node(addr));
+                (
+                    resolution,
+                    node.is_some(),
+                    node.is_some_and(|n| n.is_final()),
+                )
+            })
+            .collect();
+
+        ContainsExplanation { steps }
+    }
+
+    /// Returns true if at least one of the given cell indexes is contained
+    /// (as with `contains`) in the set.
+    ///
+    /// The lookup stops at the first match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(cell))?;
+    ///
+    /// let candidates = [
+    ///     CellIndex::try_from(0x85283473fffffff)?,
+    ///     CellIndex::try_from(0x8b1fb46622d8fff)?,
+    /// ];
+    /// assert!(set.contains_any(candidates));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn contains_any(
+        &self,
+        cells: impl IntoIterator<Item = CellIndex>,
+    ) -> bool {
+        cells.into_iter().any(|cell| self.contains(cell).is_some())
+    }
+
+    /// Returns the cells of the set covering the given cell indexes.
+    ///
+    /// Every cell index is looked up (as with `contains`) and the matching
+    /// cells, i.e. the cell indexes themselves or their ancestor, are returned
+    /// in lexicographic order, without duplicates. Misses are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let cell = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(cell))?;
+    ///
+    /// let candidates = [
+    ///     CellIndex::try_from(0x8b1fb46622d8fff)?,
+    ///     CellIndex::try_from(0x85283473fffffff)?,
+    ///     CellIndex::try_from(0x8d1fb46622d85bf)?,
+    /// ];
+    /// assert_eq!(set.present_subset(candidates), vec![cell]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn present_subset(
+        &self,
+        cells: impl IntoIterator<Item = CellIndex>,
+    ) -> Vec<CellIndex> {
+        let mut matches = cells
+            .into_iter()
+            .filter_map(|cell| self.contains(cell))
+            .collect::<Vec<_>>();
+        matches.sort_unstable_by_key(|&cell| Key::from(cell));
+        matches.dedup();
+        matches
+    }
+
+    /// Returns the cells of the set covering the neighbors of the given cell
+    /// index (the cell itself excluded).
+    ///
+    /// Like `present_subset`, the matching cells (i.e. the neighbors or their
+    /// ancestor) are returned in lexicographic order, without duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let neighbor = CellIndex::try_from(0x8a1fb46622d7fff)?;
+    /// let set = FrozenSet::try_from_iter([neighbor, index])?;
+    ///
+    /// assert_eq!(set.present_neighbors(index), vec![neighbor]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn present_neighbors(&self, index: CellIndex) -> Vec<CellIndex> {
+        self.present_subset(
+            index
+                .grid_disk::<Vec<_>>(1)
+                .into_iter()
+                .filter(|&cell| cell != index),
+        )
+    }
+
+    /// Returns up to `k` cells, at the given resolution, covered by the set
+    /// and closest to the given coordinate.
+    ///
+    /// The grid rings around the query cell are explored outward, up to
+    /// `max_k_rings`, and the matching cells are sorted by grid distance (ties
+    /// being returned in lexicographic order).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, LatLng, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let neighbor = CellIndex::try_from(0x8a1fb46622d7fff)?;
+    /// let set = FrozenSet::try_from_iter([neighbor])?;
+    ///
+    /// let coord = LatLng::from(index);
+    /// let cells = set.k_nearest(coord, Resolution::Ten, 5, 2);
+    /// assert_eq!(cells, vec![neighbor]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn k_nearest(
+        &self,
+        coord: LatLng,
+        resolution: Resolution,
+        k: usize,
+        max_k_rings: u32,
+    ) -> Vec<CellIndex> {
+        let origin = coord.to_cell(resolution);
+        let mut found = Vec::new();
+
+        // Cells are yielded ring by ring, so we can stop as soon as we have
+        // enough cells and the ring they belong to has been fully explored.
+        for (cell, distance) in origin.grid_disk_distances_safe(max_k_rings) {
+            if found.len() >= k
+                && found.last().map_or(true, |&(_, last)| last < distance)
+            {
+                break;
+            }
+            if self.contains(cell).is_some() {
+                found.push((cell, distance));
+            }
+        }
+
+        found.sort_unstable_by_key(|&(cell, distance)| {
+            (distance, Key::from(cell))
+        });
+        found.into_iter().take(k).map(|(cell, _)| cell).collect()
+    }
+
+    /// Return a lexicographically ordered stream of every descendant (present
+    /// in the set) of the given cell index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// for cell in set.descendants(index) {
+    ///     println!("{cell}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn descendants(
+        &self,
+        index: CellIndex,
+    ) -> impl FusedIterator<Item = CellIndex> + '_ {
+        self.descendants_stream(index).map_or_else(
+            || Either::Left(std::iter::empty()),
+            |stream| Either::Right(FrozenSetRangeIterator::new(stream)),
+        )
+    }
+
+    /// Return a lexicographically ordered stream of every descendant (present
+    /// in the set) of any of the given cell indexes.
+    ///
+    /// Each cell is yielded once, even if the parents overlap (i.e. one is an
+    /// ancestor of another).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let a = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let b = CellIndex::try_from(0x85318d93fffffff)?;
+    /// let set = FrozenSet::try_from_iter(
+    ///     a.children(Resolution::Six).chain(b.children(Resolution::Six)),
+    /// )?;
+    ///
+    /// assert_eq!(set.descendants_of_many([b, a]).count(), 14);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn descendants_of_many(
+        &self,
+        parents: impl IntoIterator<Item = CellIndex>,
+    ) -> impl Iterator<Item = CellIndex> + '_ {
+        let mut bounds = parents
+            .into_iter()
+            .filter_map(|parent| Key::from(parent).descendant_bounds())
+            .collect::<Vec<_>>();
+        bounds.sort_unstable_by_key(|&(start, _)| start);
+
+        // Nested parents have nested ranges: merge overlapping ranges so that
+        // every key is read once.
+        let mut ranges: Vec<(Key, Key)> = Vec::with_capacity(bounds.len());
+        for (start, end) in bounds {
+            match ranges.last_mut() {
+                Some(last) if start < last.1 => last.1 = last.1.max(end),
+                _ => ranges.push((start, end)),
+            }
+        }
+
+        ranges.into_iter().flat_map(|(start, end)| {
+            FrozenSetRangeIterator::new(
+                self.0.range().ge(start).lt(end).into_stream(),
+            )
+        })
+    }
+
+    /// Return a lexicographically ordered stream of every descendant (present
+    /// in the set) of the given cell index, checking that each one decodes
+    /// to a valid cell index.
+    ///
+    /// Unlike `descendants`, which panics on a corrupted key, an error is
+    /// yielded instead: this is useful to safely read untrusted data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// for cell in set.try_descendants(index) {
+    ///     println!("{}", cell?);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_descendants(
+        &self,
+        index: CellIndex,
+    ) -> impl Iterator<Item = Result<CellIndex, BuildError>> + '_ {
+        let mut stream = self.descendants_stream(index);
+        std::iter::from_fn(move || {
+            stream
+                .as_mut()?
+                .next()
+                .map(|key| Key::try_decode(key).ok_or(BuildError::InvalidKey))
+        })
+    }
+
+    /// Return a lexicographically ordered stream of the descendants (present
+    /// in the set) of the given cell index, at the given resolution, within
+    /// `k` grid steps of its center child.
+    ///
+    /// This carves a roughly circular region out of the subtree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Seven))?;
+    ///
+    /// let cells = set.descendants_within(index, Resolution::Seven, 1);
+    /// assert_eq!(cells.count(), 7);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn descendants_within(
+        &self,
+        index: CellIndex,
+        resolution: Resolution,
+        k: u32,
+    ) -> impl Iterator<Item = CellIndex> + '_ {
+        let center = index.center_child(resolution);
+        self.descendants(index).filter(move |&cell| {
+            cell.resolution() == resolution
+                && center.is_some_and(|center| {
+                    center.grid_distance(cell).is_ok_and(|distance| {
+                        i64::from(distance) <= i64::from(k)
+                    })
+                })
+        })
+    }
+
+    /// Calls `f` on every descendant (present in the set) of the given cell
+    /// index, in lexicographic order, until it returns `ControlFlow::Break`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    /// use std::ops::ControlFlow;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// let mut count = 0;
+    /// set.for_each_descendant(index, |_| {
+    ///     count += 1;
+    ///     if count == 2 {
+    ///         ControlFlow::Break(())
+    ///     } else {
+    ///         ControlFlow::Continue(())
+    ///     }
+    /// });
+    /// assert_eq!(count, 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn for_each_descendant(
+        &self,
+        index: CellIndex,
+        mut f: impl FnMut(CellIndex) -> ControlFlow<()>,
+    ) {
+        for cell in self.descendants(index) {
+            if f(cell).is_break() {
+                break;
+            }
+        }
+    }
+
+    /// Return a lexicographically ordered stream of the given cell index (if
+    /// present in the set) followed by all its descendants.
+    ///
+    /// Unlike `descendants`, the parent cell itself is included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(
+    ///     std::iter::once(index).chain(index.children(Resolution::Six)),
+    /// )?;
+    ///
+    /// assert_eq!(set.subtree(index).next(), Some(index));
+    /// assert_eq!(set.subtree(index).count(), 8);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn subtree(
+        &self,
+        parent: CellIndex,
+    ) -> impl FusedIterator<Item = CellIndex> + '_ {
+        let key = Key::from(parent);
+        let builder = self.0.range().ge(key);
+        let builder = match key.descendant_bounds() {
+            Some((_, end)) => builder.lt(end),
+            // No descendants at the finest resolution.
+            None => builder.le(key),
+        };
+        FrozenSetRangeIterator::new(builder.into_stream())
+    }
+
+    /// Returns the child of the given cell index whose subtree holds the most
+    /// cells of the set, along with that number of cells.
+    ///
+    /// Ties are resolved in favor of the first child (in lexicographic order).
+    /// If the cell index has no descendants in the set, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let child = CellIndex::try_from(0x86318d837ffffff)?;
+    /// let set = FrozenSet::try_from_iter(child.children(Resolution::Eight))?;
+    ///
+    /// assert_eq!(set.densest_child(index), Some((child, 49)));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn densest_child(
+        &self,
+        parent: CellIndex,
+    ) -> Option<(CellIndex, usize)> {
+        let resolution = parent.resolution().succ()?;
+        // Skip the per-child scans when there is nothing to count.
+        self.descendants(parent).next()?;
+
+        parent
+            .children(resolution)
+            .map(|child| (child, self.subtree(child).count()))
+            .fold(None, |densest, (child, count)| match densest {
+                Some((_, max)) if max >= count => densest,
+                _ => Some((child, count)),
+            })
+    }
+
+    /// Returns true if the set contains at least `n` descendants of the given
+    /// cell index.
+    ///
+    /// The descendants are streamed and the scan stops as soon as `n` of them
+    /// have been found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// assert!(set.has_at_least_descendants(index, 5));
+    /// assert!(!set.has_at_least_descendants(index, 10));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn has_at_least_descendants(&self, index: CellIndex, n: usize) -> bool {
+        n == 0 || self.descendants(index).nth(n - 1).is_some()
+    }
+
+    /// Return a lexicographically ordered stream of the cells on the boundary
+    /// of the set.
+    ///
+    /// A cell is on the boundary if at least one of its neighbors is not
+    /// contained in the set.
+    ///
+    /// Note that this is an expensive operation: every neighbor of every cell
+    /// in the set is looked up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let mut cells = index.grid_disk::<Vec<_>>(1);
+    /// cells.sort_unstable();
+    /// let set = FrozenSet::try_from_iter(cells)?;
+    ///
+    /// // Every cell but the center one is on the boundary.
+    /// assert_eq!(set.boundary_cells().count(), 6);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn boundary_cells(&self) -> impl Iterator<Item = CellIndex> + '_ {
+        self.iter().filter(|cell| {
+            cell.grid_disk::<Vec<_>>(1)
+                .into_iter()
+                .any(|neighbor| self.contains(neighbor).is_none())
+        })
+    }
+
+    /// Builds a map from the cells of this set and the given values.
+    ///
+    /// Values are attached to cells in order, i.e. the values must be in the
+    /// same order as `iter()`. Extra values are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If there are fewer values than cells, `BuildError::MissingValues` is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// let map = set.attach_values(0..)?;
+    /// assert_eq!(map.len(), set.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn attach_values(
+        &self,
+        values: impl IntoIterator<Item = u64>,
+    ) -> Result<FrozenMap<Vec<u8>>, BuildError> {
+        let mut builder = FrozenMapBuilder::memory();
+        let mut values = values.into_iter();
+        for cell in self {
+            let value = values.next().ok_or(BuildError::MissingValues)?;
+            builder.insert(cell, value)?;
+        }
+        Ok(builder.into_map())
+    }
+
+    /// Builds a map from the cells of this set, all associated to `value`.
+    ///
+    /// # Errors
+    ///
+    /// `BuildError` if the map construction fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// let map = set.to_map_const(7)?;
+    /// assert!(map.iter().all(|(_, value)| value == 7));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_map_const(
+        &self,
+        value: u64,
+    ) -> Result<FrozenMap<Vec<u8>>, BuildError> {
+        let mut builder = FrozenMapBuilder::memory();
+        for cell in self {
+            builder.insert(cell, value)?;
+        }
+        Ok(builder.into_map())
+    }
+
+    /// Return a lexicographically ordered stream of the runs of contiguous
+    /// cells in this set, as `(first cell, run length)`.
+    ///
+    /// Two cells are contiguous if they have the same resolution and are
+    /// consecutive in the key space, i.e. the second one is the `succ` of the
+    /// first one. Thus, a run can be expanded back by repeatedly calling
+    /// `CellIndex::succ` on its first cell.
+    ///
+    /// Note that consecutive cells are not always grid neighbors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Seven))?;
+    ///
+    /// let first = index.center_child(Resolution::Seven).expect("child");
+    /// assert_eq!(set.contiguous_runs().collect::<Vec<_>>(), vec![(first, 49)]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn contiguous_runs(
+        &self,
+    ) -> impl Iterator<Item = (CellIndex, u32)> + '_ {
+        let mut cells = self.iter().peekable();
+        std::iter::from_fn(move || {
+            let start = cells.next()?;
+            let (mut last, mut len) = (start, 1_u32);
+            while len < u32::MAX {
+                let Some(next) =
+                    cells.next_if(|&cell| Some(cell) == last.succ())
+                else {
+                    break;
+                };
+                (last, len) = (next, len + 1);
+            }
+            Some((start, len))
+        })
+    }
+
+    /// Return a lexicographically ordered stream of all cells in this set,
+    /// along with their boundary.
+    ///
+    /// Note that computing a cell boundary is relatively expensive, and a new
+    /// vector is allocated for every cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// for (cell, boundary) in set.iter_boundaries() {
+    ///     println!("{cell}: {boundary:?}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn iter_boundaries(
+        &self,
+    ) -> impl Iterator<Item = (CellIndex, Vec<LatLng>)> + '_ {
+        self.iter()
+            .map(|cell| (cell, cell.boundary().iter().copied().collect()))
+    }
+
+    /// Returns the boundaries of the cells of this set as a WKT
+    /// `MULTIPOLYGON`, one polygon per cell.
+    ///
+    /// See `write_wkt` for the details of the output, and to stream it
+    /// instead of building it in memory (the output can be large).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(index))?;
+    ///
+    /// assert!(set.to_wkt().starts_with("MULTIPOLYGON ((("));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[expect(
+        clippy::missing_panics_doc,
+        reason = "expect don't need to be documented"
+    )]
+    #[must_use]
+    pub fn to_wkt(&self) -> String {
+        let mut buffer = Vec::new();
+        self.write_wkt(&mut buffer).expect("in-memory writer");
+        String::from_utf8(buffer).expect("ASCII output")
+    }
+
+    /// Writes the boundaries of the cells of this set as a WKT
+    /// `MULTIPOLYGON`, one polygon per cell.
+    ///
+    /// Cells are written in lexicographic order, each one as a single closed
+    /// ring (i.e. the first vertex is repeated at the end) of `lng lat`
+    /// coordinates, in degrees. An empty set is written as
+    /// `MULTIPOLYGON EMPTY`.
+    ///
+    /// Note that coordinates are written as is: cells crossing the
+    /// antimeridian have longitudes wrapping around from 180 to -180, which
+    /// most GIS tools render as a polygon spanning the whole globe.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there was a problem writing to `w`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    /// use std::{fs, io};
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// # let file_path = "";
+    /// let mut wtr = io::BufWriter::new(fs::File::create(file_path)?);
+    /// set.write_wkt(&mut wtr)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn write_wkt<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        if self.is_empty() {
+            return w.write_all(b"MULTIPOLYGON EMPTY");
+        }
+
+        w.write_all(b"MULTIPOLYGON (")?;
+        for (i, cell) in self.iter().enumerate() {
+            if i != 0 {
+                w.write_all(b", ")?;
+            }
+            let boundary = cell.boundary();
+            w.write_all(b"((")?;
+            for (j, vertex) in
+                boundary.iter().chain(boundary.iter().take(1)).enumerate()
+            {
+                if j != 0 {
+                    w.write_all(b", ")?;
+                }
+                write!(w, "{} {}", vertex.lng(), vertex.lat())?;
+            }
+            w.write_all(b"))")?;
+        }
+        w.write_all(b")")
+    }
+
+    /// Returns the intersection of this set with the coverage of a polygon.
+    ///
+    /// The polygon (in degrees) is first filled with cells at `resolution`
+    /// (cells whose center is inside the polygon), then intersected with the
+    /// set: cells of the set at `resolution` or finer are kept if they fall
+    /// into the coverage, coarser ones are replaced by the coverage cells
+    /// they contain.
+    ///
+    /// The finer the resolution, the more accurately the polygon shape is
+    /// followed, but the more cells are generated to fill it.
+    ///
+    /// # Errors
+    ///
+    /// If the polygon is invalid, `BuildError::InvalidGeometry` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geo::polygon;
+    /// use h3o::{CellIndex, LatLng, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(index))?;
+    ///
+    /// let center = LatLng::from(index);
+    /// let (lat, lng) = (center.lat(), center.lng());
+    /// let triangle = polygon![
+    ///     (x: lng, y: lat),
+    ///     (x: lng + 0.1, y: lat),
+    ///     (x: lng + 0.05, y: lat + 0.1),
+    /// ];
+    /// let clipped = set.clip_to_polygon(&triangle, Resolution::Nine)?;
+    /// assert!(!clipped.is_empty());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn clip_to_polygon(
+        &self,
+        polygon: &geo::Polygon,
+        resolution: Resolution,
+    ) -> Result<FrozenSet<Vec<u8>>, BuildError> {
+        let mut tiler = h3o::geom::TilerBuilder::new(resolution).build();
+        tiler.add(polygon.clone())?;
+        let mut mask = tiler.into_coverage().collect::<Vec<_>>();
+        // At the same resolution, key order and numeric order coincide.
+        mask.sort_unstable();
+        mask.dedup();
+        let mask = FrozenSet::try_from_iter(mask)?;
+
+        let mut cells = self
+            .iter()
+            .flat_map(|cell| {
+                if cell.resolution() >= resolution {
+                    Either::Left(mask.contains(cell).map(|_| cell).into_iter())
+                } else {
+                    Either::Right(mask.descendants(cell))
+                }
+            })
+            .collect::<Vec<_>>();
+        // Overlapping cells in the set may yield out of order duplicates.
+        cells.sort_unstable_by_key(|&cell| Key::from(cell));
+        cells.dedup();
+        FrozenSet::try_from_iter(cells)
+    }
+
+    /// Returns the cells at `resolution` under `parent` that are not covered
+    /// by this set.
+    ///
+    /// Note that every child of `parent` at `resolution` is enumerated, and
+    /// there are roughly 7^n of them for a gap of `n` resolutions: keep the
+    /// gap small.
+    ///
+    /// # Errors
+    ///
+    /// This should never fail in practice, but the error from the underlying
+    /// builder is propagated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set =
+    ///     FrozenSet::try_from_iter(index.children(Resolution::Six).skip(1))?;
+    ///
+    /// let holes = set.complement_within(index, Resolution::Six)?;
+    /// assert_eq!(holes.len(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn complement_within(
+        &self,
+        parent: CellIndex,
+        resolution: Resolution,
+    ) -> Result<FrozenSet<Vec<u8>>, BuildError> {
+        FrozenSet::try_from_iter(
+            parent
+                .children(resolution)
+                .filter(|&cell| self.contains(cell).is_none()),
+        )
+    }
+
+    /// Returns the children of `parent`, at the given resolution, that are
+    /// not covered by this set.
+    ///
+    /// Same as `complement_within`, but returns a (lexicographically ordered)
+    /// vector instead of a set, which is more convenient for small gaps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set =
+    ///     FrozenSet::try_from_iter(index.children(Resolution::Six).skip(1))?;
+    ///
+    /// let missing = set.missing_children(index, Resolution::Six);
+    /// assert_eq!(missing.len(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn missing_children(
+        &self,
+        parent: CellIndex,
+        resolution: Resolution,
+    ) -> Vec<CellIndex> {
+        // Children share the same resolution, thus numeric order is also the
+        // lexicographic one.
+        parent
+            .children(resolution)
+            .filter(|&cell| self.contains(cell).is_none())
+            .collect()
+    }
+
+    /// Returns the fraction, between 0 and 1, of the cells at `resolution`
+    /// under `cell` that are covered by this set.
+    ///
+    /// If `resolution` is coarser than the cell's, the cell itself is tested.
+    ///
+    /// Note that, unless `cell` itself is covered, every child of `cell` at
+    /// `resolution` is enumerated, and there are roughly 7^n of them for a
+    /// gap of `n` resolutions: keep the gap small.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set =
+    ///     FrozenSet::try_from_iter(index.children(Resolution::Six).skip(1))?;
+    ///
+    /// let fraction = set.coverage_fraction(index, Resolution::Six);
+    /// assert!((fraction - 6. / 7.).abs() < f64::EPSILON);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "an approximation is good enough"
+    )]
+    #[must_use]
+    pub fn coverage_fraction(
+        &self,
+        cell: CellIndex,
+        resolution: Resolution,
+    ) -> f64 {
+        if self.contains(cell).is_some() {
+            return 1.;
+        }
+        let resolution = resolution.max(cell.resolution());
+        let (covered, total) =
+            cell.children(resolution)
+                .fold((0_u64, 0_u64), |acc, child| {
+                    let covered = u64::from(self.contains(child).is_some());
+                    (acc.0 + covered, acc.1 + 1)
+                });
+        covered as f64 / total as f64
+    }
+
+    /// Returns the number of cells of this set that are covered by `other`,
+    /// i.e. that are present in `other` or have an ancestor in it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Seven))?;
+    /// let mask =
+    ///     FrozenSet::try_from_iter(index.children(Resolution::Six).take(2))?;
+    ///
+    /// assert_eq!(set.overlap_count(&mask), 14);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn overlap_count<D2: AsRef<[u8]>>(
+        &self,
+        other: &FrozenSet<D2>,
+    ) -> usize {
+        self.iter()
+            .filter(|&cell| other.contains(cell).is_some())
+            .count()
+    }
+
+    /// Returns a random sample of (up to) `n` cells from this set.
+    ///
+    /// The sample is computed in a single streaming pass over the set (using
+    /// reservoir sampling), and is deterministic for a given seed.
+    ///
+    /// If `n` is greater than or equal to the size of the set, every cell is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Seven))?;
+    ///
+    /// let sample = set.sample(5, 42);
+    /// assert_eq!(sample.len(), 5);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "rand")]
+    #[must_use]
+    pub fn sample(&self, n: usize, seed: u64) -> Vec<CellIndex> {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+        let mut sample = Vec::with_capacity(n.min(self.len()));
+
+        for (i, cell) in self.iter().enumerate() {
+            if i < n {
+                sample.push(cell);
+            } else {
+                let j = rng.gen_range(0..=i);
+                if j < n {
+                    sample[j] = cell;
+                }
+            }
+        }
+        sample.sort_unstable_by_key(|cell| Key::from(*cell));
+
+        sample
+    }
+
+    /// Returns the coarsest and finest resolutions of the cells in this set,
+    /// or `None` if the set is empty.
+    ///
+    /// Resolutions are read from the key lengths, without decoding the cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// assert_eq!(
+    ///     set.resolution_range(),
+    ///     Some((Resolution::Six, Resolution::Six))
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn resolution_range(&self) -> Option<(Resolution, Resolution)> {
+        let mut stream = self.0.stream();
+        let mut range = ResolutionRange::default();
+        while let Some(key) = stream.next() {
+            range.push(key);
+        }
+        range.finish()
+    }
+
+    /// Returns the number of cells at each resolution present in the set, in
+    /// ascending resolution order.
+    ///
+    /// Resolutions without any cell are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// for (resolution, count) in set.counts_by_resolution() {
+    ///     println!("{resolution}: {count}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn counts_by_resolution(
+        &self,
+    ) -> impl Iterator<Item = (Resolution, usize)> {
+        // Keys are grouped by base cell, not by resolution: count everything
+        // before yielding anything.
+        let mut counts = [0; 16];
+        let mut stream = self.0.stream();
+        while let Some(key) = stream.next() {
+            // One byte for the base cell, then one per resolution.
+            counts[key.len() - 1] += 1;
+        }
+        Resolution::range(Resolution::Zero, Resolution::Fifteen)
+            .zip(counts)
+            .filter(|&(_, count)| count != 0)
+    }
+
+    /// Returns true if the set is compacted.
+    ///
+    /// A set is compacted if no cell has one of its ancestors in the set, and
+    /// no parent has all of its children in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(index))?;
+    /// assert!(set.is_compacted());
+    ///
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    /// assert!(!set.is_compacted());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn is_compacted(&self) -> bool {
+        compact::is_compacted(self)
+    }
+
+    /// Returns the smallest set of cells covering the same area as this set.
+    ///
+    /// Complete sets of siblings are recursively replaced by their parent,
+    /// while incomplete ones are kept as is. Cells already covered by one of
+    /// their ancestors are dropped.
+    ///
+    /// This is the same compaction as `FrozenSetBuilder::finish_compacted`,
+    /// applied to an existing set: the result is always compacted (see
+    /// `is_compacted`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resulting set cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Seven))?;
+    ///
+    /// let cover = set.minimal_cover()?;
+    /// assert_eq!(cover.iter().collect::<Vec<_>>(), vec![index]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn minimal_cover(&self) -> Result<FrozenSet<Vec<u8>>, BuildError> {
+        FrozenSet::try_from_iter(compact::compact(self))
+    }
+
+    /// Returns the union of this set and `other`, compacted.
+    ///
+    /// Unlike a plain union, cells covered by an ancestor from the other set
+    /// are dropped and complete sets of siblings are replaced by their parent
+    /// (see `minimal_cover`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resulting set cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let a =
+    ///     FrozenSet::try_from_iter(index.children(Resolution::Six).take(3))?;
+    /// let b =
+    ///     FrozenSet::try_from_iter(index.children(Resolution::Six).skip(3))?;
+    ///
+    /// let union = a.union_compacted(&b)?;
+    /// assert_eq!(union.iter().collect::<Vec<_>>(), vec![index]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn union_compacted<D2: AsRef<[u8]>>(
+        &self,
+        other: &FrozenSet<D2>,
+    ) -> Result<FrozenSet<Vec<u8>>, BuildError> {
+        let mut union = self.0.op().add(&other.0).union();
+        let cells = std::iter::from_fn(|| {
+            union.next().map(|key| CellIndex::from(Key::from(key)))
+        });
+        FrozenSet::try_from_iter(compact::compact(cells))
+    }
+
+    /// Returns a copy of this set where complete sets of siblings are
+    /// replaced by their parent, but only one level up.
+    ///
+    /// Unlike `minimal_cover`, the merge isn't recursive: the parents created
+    /// this way are never merged further, even if they form a complete set of
+    /// siblings themselves.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resulting set cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Seven))?;
+    ///
+    /// let merged = set.merge_one_level()?;
+    /// assert_eq!(merged.len(), 7);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn merge_one_level(&self) -> Result<FrozenSet<Vec<u8>>, BuildError> {
+        let parent_of = |cell: CellIndex| {
+            cell.resolution().pred().and_then(|res| cell.parent(res))
+        };
+
+        // Siblings aren't always contiguous (descendants of a sibling come in
+        // between), thus count the children of every parent upfront.
+        let mut counts = HashMap::<CellIndex, u64>::new();
+        for parent in self.iter().filter_map(parent_of) {
+            *counts.entry(parent).or_default() += 1;
+        }
+
+        let mut cells = self
+            .iter()
+            .map(|cell| {
+                parent_of(cell)
+                    .filter(|parent| {
+                        counts.get(parent).is_some_and(|&count| {
+                            count == parent.children_count(cell.resolution())
+                        })
+                    })
+                    .unwrap_or(cell)
+            })
+            .collect::<Vec<_>>();
+        cells.sort_unstable_by_key(|&cell| Key::from(cell));
+        cells.dedup();
+
+        FrozenSet::try_from_iter(cells)
+    }
+
+    /// Returns a copy of this set where every cell is at `resolution`.
+    ///
+    /// Finer cells are replaced by their ancestor at `resolution`, and
+    /// coarser cells by their descendants at `resolution`.
+    ///
+    /// Note that a cell has roughly 7^n descendants `n` resolutions down:
+    /// rescaling coarse cells to a fine resolution can produce a huge set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resulting set cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(index))?;
+    ///
+    /// assert_eq!(set.rescale(Resolution::Six)?.len(), 7);
+    /// assert_eq!(set.rescale(Resolution::Four)?.len(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn rescale(
+        &self,
+        resolution: Resolution,
+    ) -> Result<FrozenSet<Vec<u8>>, BuildError> {
+        let mut cells = self
+            .iter()
+            .flat_map(|cell| {
+                cell.parent(resolution).map_or_else(
+                    || Either::Right(cell.children(resolution)),
+                    |parent| Either::Left(std::iter::once(parent)),
+                )
+            })
+            .collect::<Vec<_>>();
+        // At the same resolution, key order and numeric order coincide.
+        cells.sort_unstable();
+        cells.dedup();
+        FrozenSet::try_from_iter(cells)
+    }
+
+    /// Returns true if every key of the set is a valid H3 cell index, and
+    /// keys are in strictly ascending order.
+    ///
+    /// Unlike `read_verified`, which detects corrupted bytes through the
+    /// checksum, this checks the content itself: this is useful for data that
+    /// wasn't written by a `FrozenSetBuilder`.
+    ///
+    /// Note that the whole set is read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    /// assert!(set.is_valid());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        let mut stream = self.0.stream();
+        let mut previous: Option<Vec<u8>> = None;
+        while let Some(key) = stream.next() {
+            if Key::try_decode(key).is_none()
+                || previous.as_deref().is_some_and(|previous| previous >= key)
+            {
+                return false;
+            }
+            previous = Some(key.to_vec());
+        }
+        true
+    }
+
+    /// Return a lexicographically ordered stream of all cells in this set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// for cell in set.iter() {
+    ///     println!("{cell}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn iter(&self) -> FrozenSetIterator<'_> {
+        FrozenSetIterator::new(self)
+    }
+
+    /// Collects every cell of this set, in lexicographic order, into a
+    /// vector allocated once and for all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// assert_eq!(set.to_vec().len(), 7);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_vec(&self) -> Vec<CellIndex> {
+        let mut cells = Vec::with_capacity(self.len());
+        cells.extend(self.iter());
+        cells
+    }
+
+    /// Return a lexicographically ordered stream of all cells in this set, as
+    /// raw 64-bit H3 indexes.
+    ///
+    /// The keys are decoded straight to `u64`, skipping the `CellIndex`
+    /// validation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// for cell in set.iter_u64() {
+    ///     println!("{cell:x}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn iter_u64(&self) -> impl Iterator<Item = u64> + '_ {
+        let mut stream = self.0.stream();
+        std::iter::from_fn(move || {
+            stream.next().map(|key| u64::from(Key::from(key)))
+        })
+    }
+
+    /// Return a lexicographically ordered stream of all cells in this set,
+    /// along with their raw key bytes.
+    ///
+    /// This is a debugging helper, useful to understand why the lexicographic
+    /// order of the keys differs from the numeric order of the cell indexes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// for (cell, key) in set.iter_debug() {
+    ///     println!("{cell} = {key:?}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn iter_debug(
+        &self,
+    ) -> impl Iterator<Item = (CellIndex, Vec<u8>)> + '_ {
+        let mut stream = self.0.stream();
+        std::iter::from_fn(move || {
+            stream
+                .next()
+                .map(|key| (CellIndex::from(Key::from(key)), key.to_vec()))
+        })
+    }
+
+    /// Returns a compact binary encoding of the cells of this set.
+    ///
+    /// Unlike the FST, which is optimized for lookups, this only stores the
+    /// cell indexes (as varint-encoded deltas): this can be smaller for sparse
+    /// sets, e.g. to send them over the wire.
+    ///
+    /// Use `from_packed_cells` to rebuild the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// let bytes = set.to_packed_cells();
+    /// let unpacked = FrozenSet::from_packed_cells(&bytes)?;
+    /// assert_eq!(unpacked.len(), set.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn to_packed_cells(&self) -> Vec<u8> {
+        let mut cells = self.iter_u64().collect::<Vec<_>>();
+        // Key order isn't numeric order when resolutions are mixed.
+        cells.sort_unstable();
+        packed::encode(&cells)
+    }
+
+    /// Return a lexicographically ordered stream over the subset of keys the
+    /// specified range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    /// use std::ops::Bound;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// let start = Bound::Included(CellIndex::try_from(0x86318d817ffffff)?);
+    /// let end = Bound::Excluded(CellIndex::try_from(0x86318d827ffffff)?);
+    ///
+    /// for cell in set.range((start, end)) {
+    ///     println!("{cell}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn range(
+        &self,
+        range: impl RangeBounds<CellIndex>,
+    ) -> impl FusedIterator<Item = CellIndex> + '_ {
+        if matches!(
+            (range.start_bound(), range.end_bound()),
+            (Bound::Unbounded, Bound::Unbounded)
+        ) {
+            return Either::Left(self.iter());
+        }
+        Either::Right(FrozenSetRangeIterator::new(self.range_stream(range)))
+    }
+
+    /// Return a lexicographically ordered stream of the cells starting from
+    /// the given cell index (included).
+    ///
+    /// This is a shorthand for an unbounded `range`, useful for paging.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Seven))?;
+    ///
+    /// // Fetch the next page, after the last seen cell.
+    /// let last = CellIndex::try_from(0x87318d800ffffff)?;
+    /// let page = set.iter_from(last).skip(1).take(10).collect::<Vec<_>>();
+    /// assert_eq!(page.len(), 10);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn iter_from(
+        &self,
+        start: CellIndex,
+    ) -> impl FusedIterator<Item = CellIndex> + '_ {
+        self.range((Bound::Included(start), Bound::Unbounded))
+    }
+
+    /// Returns up to `limit` cells, in lexicographic order, strictly after
+    /// `after` (or from the start of the set).
+    ///
+    /// The last returned cell is also returned as a resume token, to be
+    /// passed as `after` to fetch the next page, or `None` when there are no
+    /// more cells. Since a page is never empty unless the set is exhausted,
+    /// `limit` can't be zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Seven))?;
+    ///
+    /// let limit = NonZeroUsize::new(10).expect("non-zero limit");
+    /// let mut cells = Vec::new();
+    /// let mut token = None;
+    /// loop {
+    ///     let (page, next) = set.page(token, limit);
+    ///     cells.extend(page);
+    ///     token = next;
+    ///     if token.is_none() {
+    ///         break;
+    ///     }
+    /// }
+    /// assert_eq!(cells.len(), set.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn page(
+        &self,
+        after: Option<CellIndex>,
+        limit: NonZeroUsize,
+    ) -> (Vec<CellIndex>, Option<CellIndex>) {
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+        let mut iter = self.range((start, Bound::Unbounded)).peekable();
+        let cells = iter.by_ref().take(limit.get()).collect::<Vec<_>>();
+        let token = iter.peek().and_then(|_| cells.last().copied());
+        (cells, token)
+    }
+
+    /// Return a lexicographically ordered stream of the cells under the given
+    /// base cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// assert_eq!(set.iter_base_cell(index.base_cell().into()).count(), 7);
+    /// assert_eq!(set.iter_base_cell(0).count(), 0);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn iter_base_cell(
+        &self,
+        base: u8,
+    ) -> impl Iterator<Item = CellIndex> + '_ {
+        // Keys starts with the base cell, so it's a simple prefix range.
+        let builder = self.0.range().ge([base]);
+        let builder = match base.checked_add(1) {
+            Some(next) => builder.lt([next]),
+            None => builder,
+        };
+        FrozenSetRangeIterator::new(builder.into_stream())
+    }
+
+    /// Return a lexicographically ordered stream of the cells that have no
+    /// descendant in this set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(
+    ///     std::iter::once(index).chain(index.children(Resolution::Six)),
+    /// )?;
+    ///
+    /// assert_eq!(set.leaves().count(), 7);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn leaves(&self) -> impl Iterator<Item = CellIndex> + '_ {
+        let mut iter = self.iter().peekable();
+        std::iter::from_fn(move || loop {
+            let cell = iter.next()?;
+            // In lexicographic order, descendants come right after their
+            // ancestor.
+            if !iter
+                .peek()
+                .is_some_and(|&next| compact::is_ancestor(cell, next))
+            {
+                return Some(cell);
+            }
+        })
+    }
+
+    /// Return a lexicographically ordered stream over the subset of keys
+    /// between the corners of a bounding box.
+    ///
+    /// The corners are converted to cells at the given resolution, and the
+    /// cells whose keys fall between those two cells are returned.
+    ///
+    /// Note that the key order doesn't follow a 2D order: this is only an
+    /// approximate prefilter, some of the returned cells may lie outside the
+    /// bounding box (and cells inside the box may be missing). The caller is
+    /// responsible for testing the exact containment of the cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, LatLng, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// let sw = LatLng::new(30.0, 120.0)?;
+    /// let ne = LatLng::new(31.0, 121.0)?;
+    ///
+    /// for cell in set.range_bbox(sw, ne, Resolution::Six) {
+    ///     println!("{cell}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn range_bbox(
+        &self,
+        sw: LatLng,
+        ne: LatLng,
+        resolution: Resolution,
+    ) -> impl Iterator<Item = CellIndex> + '_ {
+        let (sw, ne) = (sw.to_cell(resolution), ne.to_cell(resolution));
+        // At the same resolution, key order and numeric order coincide.
+        let (start, end) = (sw.min(ne), sw.max(ne));
+
+        self.range((Bound::Included(start), Bound::Included(end)))
+    }
+
+    /// Create a new set made of the cells of this set and the given ones.
+    ///
+    /// The new cells must be yielded in lexicographic order, but they can be
+    /// interleaved with the existing ones. Cells already present in the set
+    /// are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If the iterator does not yield values in lexicographic order, then an
+    /// error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// let cell = CellIndex::try_from(0x85283473fffffff)?;
+    /// let set = set.append(std::iter::once(cell))?;
+    /// assert_eq!(set.len(), 8);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn append(
+        &self,
+        new_cells: impl IntoIterator<Item = CellIndex>,
+    ) -> Result<FrozenSet<Vec<u8>>, BuildError> {
+        let mut builder = FrozenSetBuilder::memory();
+        let mut existing = self.iter().peekable();
+
+        for cell in new_cells {
+            let key = Key::from(cell);
+            while let Some(current) =
+                existing.next_if(|current| Key::from(*current) < key)
+            {
+                builder.insert_dedup(current)?;
+            }
+            builder.insert_dedup(cell)?;
+        }
+        for current in existing {
+            builder.insert_dedup(current)?;
+        }
+
+        FrozenSet::new(builder.into_inner()?)
+    }
+
+    /// Returns the binary contents of this set.
+    pub(crate) fn fst_bytes(&self) -> &[u8] {
+        self.0.as_fst().as_bytes()
+    }
+
+    /// Returns a raw stream over the keys of the descendants of the given
+    /// cell index.
+    fn descendants_stream(&self, index: CellIndex) -> Option<Stream<'_>> {
+        // If there is no lower resolution there can't be any descendants.
+        let (start, end) = Key::from(index).descendant_bounds()?;
+        Some(self.0.range().ge(start).lt(end).into_stream())
+    }
+
+    /// Returns a raw stream over the keys in the specified range.
+    fn range_stream(&self, range: impl RangeBounds<CellIndex>) -> Stream<'_> {
+        let builder = self.0.range();
+        let builder = match range.start_bound() {
+            Bound::Included(lower) => builder.ge(Key::from(*lower)),
+            Bound::Excluded(lower) => builder.gt(Key::from(*lower)),
+            Bound::Unbounded => builder,
+        };
+        let builder = match range.end_bound() {
+            Bound::Included(upper) => builder.le(Key::from(*upper)),
+            Bound::Excluded(upper) => builder.lt(Key::from(*upper)),
+            Bound::Unbounded => builder,
+        };
+        builder.into_stream()
+    }
+}
+
+impl FrozenSet<Vec<u8>> {
+    /// Create a `FrozenSet` from an iterator of ordered H3 cell indexes.
+    ///
+    /// Note that this is a convenience function to build a set in memory.
+    /// To build a set that streams to an arbitrary `io::Write`, use
+    /// `FrozenSetBuilder`.
+    ///
+    /// # Errors
+    ///
+    /// If the iterator does not yield values in lexicographic order, then an
+    /// error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_iter(
+        iter: impl IntoIterator<Item = CellIndex>,
+    ) -> Result<Self, BuildError> {
+        let mut builder = FrozenSetBuilder::memory();
+        builder.extend_iter(iter)?;
+        Self::new(builder.into_inner()?)
+    }
+
+    /// Create a `FrozenSet` from an iterator of ordered H3 cell indexes, each
+    /// one flagged for inclusion (e.g. the pixels of a boolean raster).
+    ///
+    /// Only the cell indexes flagged with `true` are added to the set.
+    ///
+    /// # Errors
+    ///
+    /// If the iterator does not yield values in lexicographic order, flagged
+    /// or not, then an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let set = FrozenSet::try_from_mask(
+    ///     index.children(Resolution::Six).zip([true, false].repeat(4)),
+    /// )?;
+    /// assert_eq!(set.len(), 4);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_mask(
+        cells: impl IntoIterator<Item = (CellIndex, bool)>,
+    ) -> Result<Self, BuildError> {
+        let mut builder = FrozenSetBuilder::memory();
+        let mut previous = None;
+        for (cell, flag) in cells {
+            // Unflagged cells must be in order too.
+            if let Some(previous) = previous
+                .filter(|&previous| Key::from(cell) < Key::from(previous))
+            {
+                return Err(BuildError::OutOfOrder {
+                    previous,
+                    attempted: cell,
+                });
+            }
+            previous = Some(cell);
+
+            if flag {
+                builder.insert(cell)?;
+            }
+        }
+        Self::new(builder.into_inner()?)
+    }
+
+    /// Create a `FrozenSet` from groups of H3 cell indexes, keyed by base
+    /// cell.
+    ///
+    /// Cells within a group can be in any order (they are sorted internally),
+    /// but groups must be provided in ascending base cell order, and each
+    /// group must only contain cells from its base cell.
+    ///
+    /// # Errors
+    ///
+    /// If the base cells are not in ascending order, or if the cells are not
+    /// grouped by base cell, then `BuildError::InvalidGroup` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let set = FrozenSet::try_from_grouped([
+    ///     (15, vec![CellIndex::try_from(0x8a1fb46622dffff)?]),
+    ///     (
+    ///         24,
+    ///         vec![
+    ///             CellIndex::try_from(0x85318d93fffffff)?,
+    ///             CellIndex::try_from(0x85318d83fffffff)?,
+    ///         ],
+    ///     ),
+    /// ])?;
+    /// assert_eq!(set.len(), 3);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_grouped(
+        groups: impl IntoIterator<Item = (u8, Vec<CellIndex>)>,
+    ) -> Result<Self, BuildError> {
+        let mut builder = FrozenSetBuilder::memory();
+        let mut previous = None;
+        for (base, mut cells) in groups {
+            if previous.is_some_and(|previous| previous >= base)
+                || cells.iter().any(|cell| u8::from(cell.base_cell()) != base)
+            {
+                return Err(BuildError::InvalidGroup { base_cell: base });
+            }
+            previous = Some(base);
+
+            cells.sort_unstable_by_key(|&cell| Key::from(cell));
+            builder.extend_iter(cells)?;
+        }
+        Self::new(builder.into_inner()?)
+    }
+
+    /// Create a `FrozenSet` from an iterator of ordered, inclusive, ranges of
+    /// H3 cell indexes.
+    ///
+    /// Both ends of a range must be at the same resolution, and every cell in
+    /// between is inserted. A range whose start is after its end is empty.
+    ///
+    /// # Errors
+    ///
+    /// If a range spans several resolutions, `BuildError::MixedResolutions`
+    /// is returned. If the ranges overlap or are not in lexicographic order,
+    /// then an error is returned as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let children = index.children(Resolution::Six).collect::<Vec<_>>();
+    /// let set = FrozenSet::try_from_ranges([(children[0], children[6])])?;
+    /// assert_eq!(set.len(), 7);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_ranges(
+        ranges: impl IntoIterator<Item = (CellIndex, CellIndex)>,
+    ) -> Result<Self, BuildError> {
+        let mut builder = FrozenSetBuilder::memory();
+        for (start, end) in ranges {
+            if start.resolution() != end.resolution() {
+                return Err(BuildError::MixedResolutions);
+            }
+            // At a given resolution, numeric order is also the lexicographic
+            // one.
+            builder.extend_iter(
+                std::iter::successors(Some(start), |cell| cell.succ())
+                    .take_while(|&cell| cell <= end),
+            )?;
+        }
+        Self::new(builder.into_inner()?)
+    }
+
+    /// Create a `FrozenSet` covering the polygons of a `GeoJSON` feature
+    /// collection, at the given resolution.
+    ///
+    /// Every `Polygon` and `MultiPolygon` feature is filled with cells, and
+    /// the resulting set is the union of those fillings. Features with
+    /// another geometry type (or without geometry) are skipped, as they don't
+    /// cover an area.
+    ///
+    /// # Errors
+    ///
+    /// If a polygon is invalid, `BuildError::InvalidGeometry` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::FeatureCollection;
+    /// use h3o::Resolution;
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let fc = r#"{
+    ///     "type": "FeatureCollection",
+    ///     "features": [{
+    ///         "type": "Feature",
+    ///         "properties": {},
+    ///         "geometry": {
+    ///             "type": "Polygon",
+    ///             "coordinates": [[
+    ///                 [2.35, 48.85], [2.36, 48.85], [2.36, 48.86], [2.35, 48.85]
+    ///             ]]
+    ///         }
+    ///     }]
+    /// }"#
+    /// .parse::<FeatureCollection>()?;
+    /// let set = FrozenSet::try_from_geojson(&fc, Resolution::Nine)?;
+    /// assert!(!set.is_empty());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(feature = "geojson")]
+    pub fn try_from_geojson(
+        fc: &geojson::FeatureCollection,
+        resolution: Resolution,
+    ) -> Result<Self, BuildError> {
+        let mut tiler = h3o::geom::TilerBuilder::new(resolution).build();
+        for geometry in fc.features.iter().filter_map(|f| f.geometry.as_ref()) {
+            match geo::Geometry::try_from(&geometry.value) {
+                Ok(geo::Geometry::Polygon(polygon)) => tiler.add(polygon)?,
+                Ok(geo::Geometry::MultiPolygon(polygons)) => {
+                    tiler.add_batch(polygons)?;
+                }
+                _ => (),
+            }
+        }
+        let mut cells = tiler.into_coverage().collect::<Vec<_>>();
+        // At the same resolution, key order and numeric order coincide.
+        cells.sort_unstable();
+        cells.dedup();
+        Self::try_from_iter(cells)
+    }
+
+    /// Create a `FrozenSet` from newline-delimited H3 cell indexes, in
+    /// hexadecimal form.
+    ///
+    /// Lines can be in any order (they are sorted internally) and may contain
+    /// duplicates. Surrounding whitespace is ignored, as well as blank lines.
+    ///
+    /// # Errors
+    ///
+    /// If a line isn't a valid cell index, `BuildError::InvalidCell` is
+    /// returned with the offending line number.
+    ///
+    /// If there was a problem reading from `rdr`, `BuildError::Io` is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o_ice::FrozenSet;
+    ///
+    /// let input = "85318d83fffffff\n8a1fb46622dffff\n";
+    /// let set = FrozenSet::try_from_reader_lines(input.as_bytes())?;
+    /// assert_eq!(set.len(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_from_reader_lines(
+        rdr: impl io::BufRead,
+    ) -> Result<Self, BuildError> {
+        let mut cells = Vec::new();
+        for (idx, line) in rdr.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let cell = line.parse::<CellIndex>().map_err(|source| {
+                BuildError::InvalidCell {
+                    line: idx + 1,
+                    source,
+                }
+            })?;
+            cells.push(cell);
+        }
+        cells.sort_unstable_by_key(|&cell| Key::from(cell));
+        cells.dedup();
+        Self::try_from_iter(cells)
+    }
+
+    /// Create a `FrozenSet` from cells packed by `FrozenSet::to_packed_cells`.
+    ///
+    /// # Errors
+    ///
+    /// If the bytes aren't a valid encoding of cell indexes,
+    /// `BuildError::InvalidPackedCells` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSet;
     ///
-    /// for cell in set.descendants(index) {
-    ///     println!("{cell}");
-    /// }
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let set = FrozenSet::try_from_iter(std::iter::once(index))?;
+    ///
+    /// let unpacked = FrozenSet::from_packed_cells(&set.to_packed_cells())?;
+    /// assert_eq!(unpacked.iter().collect::<Vec<_>>(), vec![index]);
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    #[expect(
-        clippy::missing_panics_doc,
-        reason = "expect don't need to be documented"
-    )]
-    pub fn descendants(
-        &self,
-        index: CellIndex,
-    ) -> impl Iterator<Item = CellIndex> + '_ {
-        index.resolution().succ().map_or_else(
-            // If there is no lower resolution there can't be any descendants.
-            || Either::Left(std::iter::empty()),
-            |resolution| {
-                let mut children = index.children(resolution);
-                let start = children.next().expect("first child");
-                let end = children.last().expect("last child");
-                Either::Right(
-                    self.range((Bound::Included(start), Bound::Included(end))),
-                )
-            },
-        )
+    pub fn from_packed_cells(bytes: &[u8]) -> Result<Self, BuildError> {
+        let mut cells = packed::decode(bytes)
+            .and_then(|values| {
+                values
+                    .into_iter()
+                    .map(|value| CellIndex::try_from(value).ok())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .ok_or(BuildError::InvalidPackedCells)?;
+        cells.sort_unstable_by_key(|&cell| Key::from(cell));
+        cells.dedup();
+        Self::try_from_iter(cells)
     }
 
-    /// Return a lexicographically ordered stream of all cells in this set.
+    /// Returns the binary contents of this set.
     ///
     /// # Examples
     ///
-    /// ```
+    /// ```no_run
     /// use h3o::{CellIndex, Resolution};
     /// use h3o_ice::FrozenSet;
     ///
     /// let index = CellIndex::try_from(0x85318d83fffffff)?;
     /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
     ///
-    /// for cell in set.iter() {
-    ///     println!("{cell}");
-    /// }
+    /// # let file_path = "";
+    /// std::fs::write(file_path, set.as_bytes())?;
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     #[must_use]
-    pub fn iter(&self) -> FrozenSetIterator<'_> {
-        FrozenSetIterator::new(self)
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_fst().as_bytes()
     }
 
-    /// Return a lexicographically ordered stream over the subset of keys the
-    /// specified range.
+    /// Returns a copy of this set backed by a shared buffer.
+    ///
+    /// The bytes are copied once, after which cloning the returned set is
+    /// only a reference count increment. This is handy to share a set across
+    /// threads.
     ///
     /// # Examples
     ///
     /// ```
     /// use h3o::{CellIndex, Resolution};
     /// use h3o_ice::FrozenSet;
-    /// use std::ops::Bound;
     ///
     /// let index = CellIndex::try_from(0x85318d83fffffff)?;
     /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
     ///
-    /// let start = Bound::Included(CellIndex::try_from(0x86318d817ffffff)?);
-    /// let end = Bound::Excluded(CellIndex::try_from(0x86318d827ffffff)?);
-    ///
-    /// for cell in set.range((start, end)) {
-    ///     println!("{cell}");
-    /// }
+    /// let shared = set.shared();
+    /// let handle = std::thread::spawn({
+    ///     let set = shared.clone();
+    ///     move || set.len()
+    /// });
+    /// assert_eq!(handle.join().expect("thread"), shared.len());
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn range(
-        &self,
-        range: impl RangeBounds<CellIndex>,
-    ) -> impl Iterator<Item = CellIndex> + '_ {
-        let (start, end) = (range.start_bound(), range.end_bound());
-
-        if matches!((start, end), (Bound::Unbounded, Bound::Unbounded)) {
-            return Either::Left(self.iter());
-        }
-        let builder = self.0.range();
-        let builder = match start {
-            Bound::Included(lower) => builder.ge(Key::from(*lower)),
-            Bound::Excluded(lower) => builder.gt(Key::from(*lower)),
-            Bound::Unbounded => builder,
-        };
-        let builder = match end {
-            Bound::Included(upper) => builder.le(Key::from(*upper)),
-            Bound::Excluded(upper) => builder.lt(Key::from(*upper)),
-            Bound::Unbounded => builder,
-        };
-        Either::Right(FrozenSetRangeIterator::new(builder.into_stream()))
+    #[expect(
+        clippy::missing_panics_doc,
+        reason = "expect don't need to be documented"
+    )]
+    #[must_use]
+    pub fn shared(&self) -> FrozenSet<Arc<[u8]>> {
+        FrozenSet::from_arc(Arc::from(self.as_bytes())).expect("valid set")
     }
 }
 
-impl FrozenSet<Vec<u8>> {
-    /// Create a `FrozenSet` from an iterator of ordered H3 cell indexes.
+impl FrozenSet<Arc<[u8]>> {
+    /// Creates a set from a shared raw byte sequence.
     ///
-    /// Note that this is a convenience function to build a set in memory.
-    /// To build a set that streams to an arbitrary `io::Write`, use
-    /// `FrozenSetBuilder`.
+    /// Cloning the resulting set is only a reference count increment.
     ///
     /// # Errors
     ///
-    /// If the iterator does not yield values in lexicographic order, then an
-    /// error is returned.
+    /// The set must have been written with a compatible builder. If the format
+    /// is invalid or if there is a mismatch between the API version of this
+    /// library and the set, then an error is returned.
     ///
     /// # Examples
     ///
     /// ```
     /// use h3o::{CellIndex, Resolution};
     /// use h3o_ice::FrozenSet;
+    /// use std::sync::Arc;
     ///
     /// let index = CellIndex::try_from(0x85318d83fffffff)?;
     /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    ///
+    /// let bytes = Arc::<[u8]>::from(set.as_bytes());
+    /// let shared = FrozenSet::from_arc(bytes)?;
+    /// assert_eq!(shared.len(), set.len());
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    pub fn try_from_iter(
-        iter: impl IntoIterator<Item = CellIndex>,
-    ) -> Result<Self, BuildError> {
-        let mut builder = FrozenSetBuilder::memory();
-        builder.extend_iter(iter)?;
-        Self::new(builder.into_inner()?)
+    pub fn from_arc(bytes: Arc<[u8]>) -> Result<Self, BuildError> {
+        Self::new(bytes)
     }
+}
 
-    /// Returns the binary contents of this set.
+impl Default for FrozenSet<Vec<u8>> {
+    fn default() -> Self {
+        FrozenSetBuilder::memory().into_set()
+    }
+}
+
+// Sets are compared on their serialized bytes, not on their content: two
+// sets holding the same cells compare equal, but the ordering between
+// different sets has no semantic meaning.
+impl PartialEq for FrozenSet<Vec<u8>> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for FrozenSet<Vec<u8>> {}
+
+impl PartialOrd for FrozenSet<Vec<u8>> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FrozenSet<Vec<u8>> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl<'a, D: AsRef<[u8]>> IntoIterator for &'a FrozenSet<D> {
+    type IntoIter = FrozenSetIterator<'a>;
+    type Item = CellIndex;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// ------------------------------------------------------------------------------
+
+/// The steps of a membership test, as returned by
+/// `FrozenSet::explain_contains`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ContainsExplanation {
+    /// For each resolution, from the base cell down to the resolution of the
+    /// tested cell index: whether the FST has a node for the ancestor at this
+    /// resolution, and whether this node is final (i.e. the ancestor is
+    /// present in the set).
+    pub steps: Vec<(Resolution, bool, bool)>,
+}
+
+// ------------------------------------------------------------------------------
+
+/// A read-only set of H3 cell indexes, all at the same resolution.
+///
+/// Created by `FrozenSet::new_single_resolution`.
+pub struct SingleResolutionSet<D> {
+    set: FrozenSet<D>,
+    // `None` for an empty set.
+    resolution: Option<Resolution>,
+}
+
+impl<D: AsRef<[u8]>> SingleResolutionSet<D> {
+    /// Returns the resolution of the cells in this set, or `None` if the set
+    /// is empty.
+    #[must_use]
+    pub const fn resolution(&self) -> Option<Resolution> {
+        self.resolution
+    }
+
+    /// Tests the membership of a single H3 cell index.
+    ///
+    /// Returns the ancestor-or-self of the cell index present in the set, if
+    /// any, just like `FrozenSet::contains`. Since every cell is at the same
+    /// resolution, this only requires a single exact lookup.
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// use h3o::{CellIndex, Resolution};
     /// use h3o_ice::FrozenSet;
     ///
     /// let index = CellIndex::try_from(0x85318d83fffffff)?;
     /// let set = FrozenSet::try_from_iter(index.children(Resolution::Six))?;
+    /// let set = FrozenSet::new_single_resolution(set.as_bytes())?;
     ///
-    /// # let file_path = "";
-    /// std::fs::write(file_path, set.as_bytes())?;
+    /// let descendant = index.center_child(Resolution::Ten).expect("child");
+    /// let parent = descendant.parent(Resolution::Six);
+    /// assert_eq!(set.contains(descendant), parent);
+    /// assert_eq!(set.contains(index), None);
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
-    #[must_use]
-    pub fn as_bytes(&self) -> &[u8] {
-        self.0.as_fst().as_bytes()
+    pub fn contains(&self, index: CellIndex) -> Option<CellIndex> {
+        let ancestor = index.parent(self.resolution?)?;
+        self.set.0.contains(Key::from(ancestor)).then_some(ancestor)
     }
-}
 
-impl<'a, D: AsRef<[u8]>> IntoIterator for &'a FrozenSet<D> {
-    type IntoIter = FrozenSetIterator<'a>;
-    type Item = CellIndex;
+    /// Returns the underlying set.
+    #[must_use]
+    pub const fn as_set(&self) -> &FrozenSet<D> {
+        &self.set
+    }
 
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+    /// Returns the underlying set, consuming this one.
+    #[must_use]
+    pub fn into_set(self) -> FrozenSet<D> {
+        self.set
     }
 }
 
@@ -319,7 +2682,13 @@ impl<'a, D: AsRef<[u8]>> IntoIterator for &'a FrozenSet<D> {
 /// builder.finish()?;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
-pub struct FrozenSetBuilder<W>(SetBuilder<W>);
+pub struct FrozenSetBuilder<W> {
+    builder: SetBuilder<W>,
+    // Last inserted cell index, if any.
+    last: Option<CellIndex>,
+    // Resolution enforced on every inserted cell index, if any.
+    resolution: Option<Resolution>,
+}
 
 impl<W: io::Write> FrozenSetBuilder<W> {
     /// Create a builder that builds a set by writing it to `wtr` in a
@@ -330,7 +2699,12 @@ impl<W: io::Write> FrozenSetBuilder<W> {
     /// If there was a problem writing to the underlying writer, an error is
     /// returned.
     pub fn new(wtr: W) -> Result<Self, BuildError> {
-        SetBuilder::new(wtr).map(Self).map_err(Into::into)
+        let builder = SetBuilder::new(wtr)?;
+        Ok(Self {
+            builder,
+            last: None,
+            resolution: None,
+        })
     }
 
     /// Insert a new cell index into the set.
@@ -340,10 +2714,92 @@ impl<W: io::Write> FrozenSetBuilder<W> {
     /// If a cell index is inserted that is less than any previous cell index
     /// added, then an error is returned.
     ///
+    /// In strict resolution mode (see `strict_resolution`), inserting a cell
+    /// index at another resolution returns `BuildError::ResolutionMismatch`.
+    ///
     /// Similarly, if there was a problem writing to the underlying writer, an
     /// error is returned.
     pub fn insert(&mut self, index: CellIndex) -> Result<(), BuildError> {
-        self.0.insert(Key::from(index)).map_err(Into::into)
+        if let Some(expected) =
+            self.resolution.filter(|&res| res != index.resolution())
+        {
+            return Err(BuildError::ResolutionMismatch {
+                expected,
+                attempted: index,
+            });
+        }
+        if let Some(previous) = self.last {
+            if Key::from(index) < Key::from(previous) {
+                return Err(BuildError::OutOfOrder {
+                    previous,
+                    attempted: index,
+                });
+            }
+        }
+        self.builder.insert(Key::from(index))?;
+        self.last = Some(index);
+        Ok(())
+    }
+
+    /// Only accept cell indexes at the given resolution.
+    ///
+    /// This catches accidental mixes of resolutions at build time, rather
+    /// than silently producing a hierarchical set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::{BuildError, FrozenSetBuilder};
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let mut builder =
+    ///     FrozenSetBuilder::memory().strict_resolution(Resolution::Six);
+    ///
+    /// let result = builder.insert(index);
+    /// assert!(matches!(result, Err(BuildError::ResolutionMismatch { .. })));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub const fn strict_resolution(mut self, resolution: Resolution) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
+    /// Insert a new cell index into the set, unless it's the same as the
+    /// previously inserted one.
+    ///
+    /// This allows to build a set from an ordered input that may contain
+    /// consecutive duplicates.
+    ///
+    /// # Errors
+    ///
+    /// If a cell index is inserted that is less than any previous cell index
+    /// added, then an error is returned.
+    ///
+    /// Similarly, if there was a problem writing to the underlying writer, an
+    /// error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSetBuilder;
+    ///
+    /// let index = CellIndex::try_from(0x8a1fb46622dffff)?;
+    /// let mut builder = FrozenSetBuilder::memory();
+    /// builder.insert_dedup(index)?;
+    /// builder.insert_dedup(index)?;
+    ///
+    /// let set = builder.into_set();
+    /// assert_eq!(set.len(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn insert_dedup(&mut self, index: CellIndex) -> Result<(), BuildError> {
+        if self.last == Some(index) {
+            return Ok(());
+        }
+        self.insert(index)
     }
 
     /// Calls insert on each cell index in the iterator.
@@ -356,9 +2812,49 @@ impl<W: io::Write> FrozenSetBuilder<W> {
         &mut self,
         iter: impl IntoIterator<Item = CellIndex>,
     ) -> Result<(), BuildError> {
-        self.0
-            .extend_iter(iter.into_iter().map(Key::from))
-            .map_err(Into::into)
+        for index in iter {
+            self.insert(index)?;
+        }
+        Ok(())
+    }
+
+    /// Calls insert on each cell index in the iterator, keeping track of the
+    /// position of the failing one.
+    ///
+    /// Returns the number of cell indexes inserted.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurred while adding an element (e.g. an out of order
+    /// cell index), processing is stopped and the error is returned along
+    /// with the position of the rejected element, which is also the number of
+    /// cell indexes inserted so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::{BuildError, FrozenSetBuilder};
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let mut cells = index.children(Resolution::Six).collect::<Vec<_>>();
+    /// cells.swap(2, 3);
+    ///
+    /// let mut builder = FrozenSetBuilder::memory();
+    /// let result = builder.extend_checked(cells);
+    /// assert!(matches!(result, Err((3, BuildError::OutOfOrder { .. }))));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn extend_checked(
+        &mut self,
+        iter: impl IntoIterator<Item = CellIndex>,
+    ) -> Result<usize, (usize, BuildError)> {
+        let mut count = 0;
+        for index in iter {
+            self.insert(index).map_err(|err| (count, err))?;
+            count += 1;
+        }
+        Ok(count)
     }
 
     /// Finishes the construction of the set and flushes the underlying
@@ -370,7 +2866,7 @@ impl<W: io::Write> FrozenSetBuilder<W> {
     /// Returns an error if there was a problem writing to the underlying
     /// writer.
     pub fn finish(self) -> Result<(), BuildError> {
-        self.0.finish().map_err(Into::into)
+        self.builder.finish().map_err(Into::into)
     }
 
     /// Just like `finish`, except it returns the underlying writer after
@@ -381,7 +2877,7 @@ impl<W: io::Write> FrozenSetBuilder<W> {
     /// Returns an error if there was a problem writing to the underlying
     /// writer.
     pub fn into_inner(self) -> Result<W, BuildError> {
-        self.0.into_inner().map_err(Into::into)
+        self.builder.into_inner().map_err(Into::into)
     }
 }
 
@@ -390,14 +2886,124 @@ impl FrozenSetBuilder<Vec<u8>> {
     #[inline]
     #[must_use]
     pub fn memory() -> Self {
-        Self(SetBuilder::memory())
+        Self {
+            builder: SetBuilder::memory(),
+            last: None,
+            resolution: None,
+        }
+    }
+
+    /// Create a builder that builds a set in memory, with a buffer of at
+    /// least `bytes` bytes preallocated.
+    #[expect(
+        clippy::missing_panics_doc,
+        reason = "expect don't need to be documented"
+    )]
+    #[must_use]
+    pub fn with_capacity(bytes: usize) -> Self {
+        Self::new(Vec::with_capacity(bytes)).expect("in-memory writer")
+    }
+
+    /// Create a builder that builds a set in memory, with a buffer of at
+    /// least `bytes` bytes preallocated.
+    ///
+    /// Unlike `with_capacity`, allocation failures are reported instead of
+    /// aborting.
+    ///
+    /// # Errors
+    ///
+    /// If the buffer cannot be allocated, `BuildError::Alloc` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o_ice::FrozenSetBuilder;
+    ///
+    /// let builder = FrozenSetBuilder::try_with_capacity(1024)?;
+    /// let set = builder.into_set();
+    /// assert!(set.is_empty());
+    ///
+    /// assert!(FrozenSetBuilder::try_with_capacity(usize::MAX).is_err());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn try_with_capacity(bytes: usize) -> Result<Self, BuildError> {
+        let mut buffer = Vec::new();
+        buffer.try_reserve(bytes)?;
+        Self::new(buffer)
+    }
+
+    /// Discards the cells inserted so far, so that the builder can be reused
+    /// to build another set.
+    ///
+    /// The underlying buffer is cleared but retains its capacity. Note that,
+    /// to get the buffer back, the pending set is finished first: this costs
+    /// a final flush of the pending nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::CellIndex;
+    /// use h3o_ice::FrozenSetBuilder;
+    ///
+    /// let mut builder = FrozenSetBuilder::memory();
+    /// builder.insert(CellIndex::try_from(0x85318d83fffffff)?)?;
+    /// builder.reset();
+    /// builder.insert(CellIndex::try_from(0x85283473fffffff)?)?;
+    ///
+    /// let set = builder.into_set();
+    /// assert_eq!(set.len(), 1);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[expect(
+        clippy::missing_panics_doc,
+        reason = "expect don't need to be documented"
+    )]
+    pub fn reset(&mut self) {
+        // Unlike `SetBuilder::memory`, doesn't preallocate a buffer.
+        let placeholder =
+            SetBuilder::new(Vec::new()).expect("in-memory writer");
+        let builder = std::mem::replace(&mut self.builder, placeholder);
+        let mut buffer = builder.into_inner().expect("in-memory writer");
+        buffer.clear();
+        self.builder = SetBuilder::new(buffer).expect("in-memory writer");
+        self.last = None;
     }
 
     /// Finishes the construction of the set and returns it.
     #[inline]
     #[must_use]
     pub fn into_set(self) -> FrozenSet<Vec<u8>> {
-        FrozenSet(self.0.into_set())
+        FrozenSet(self.builder.into_set())
+    }
+
+    /// Finishes the construction of the set, compacts it and returns it.
+    ///
+    /// Cells covered by one of their ancestors are dropped and complete sets
+    /// of siblings are replaced by their parent.
+    ///
+    /// Note that this is only available for in-memory builders, as the whole
+    /// set must be read back to be compacted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the compacted set cannot be built.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use h3o::{CellIndex, Resolution};
+    /// use h3o_ice::FrozenSetBuilder;
+    ///
+    /// let index = CellIndex::try_from(0x85318d83fffffff)?;
+    /// let mut builder = FrozenSetBuilder::memory();
+    /// builder.extend_iter(index.children(Resolution::Seven))?;
+    ///
+    /// let set = builder.finish_compacted()?;
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![index]);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn finish_compacted(self) -> Result<FrozenSet<Vec<u8>>, BuildError> {
+        FrozenSet::try_from_iter(compact::compact(&self.into_set()))
     }
 }
 
@@ -438,6 +3044,8 @@ impl Iterator for FrozenSetIterator<'_> {
     }
 }
 
+impl FusedIterator for FrozenSetIterator<'_> {}
+
 impl ExactSizeIterator for FrozenSetIterator<'_> {
     // We can easily calculate the remaining number of iterations.
     fn len(&self) -> usize {
@@ -465,3 +3073,5 @@ impl Iterator for FrozenSetRangeIterator<'_> {
         self.stream.next().map(|key| Key::from(key).into())
     }
 }
+
+impl FusedIterator for FrozenSetRangeIterator<'_> {}
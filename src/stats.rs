@@ -0,0 +1,138 @@
+use fst::{raw::Fst, Set, Streamer};
+use h3o::Resolution;
+use std::collections::HashSet;
+
+/// Statistics about the FST underlying a set or a map.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct FstStats {
+    /// Number of nodes in the FST.
+    pub nodes: usize,
+    /// Size of the FST, in bytes.
+    pub bytes: usize,
+    /// Number of keys stored in the FST.
+    pub len: usize,
+}
+
+impl FstStats {
+    pub(crate) fn new<D: AsRef<[u8]>>(fst: &Fst<D>) -> Self {
+        // Nodes can be shared between keys, so keep track of the visited ones.
+        let mut visited = HashSet::new();
+        let mut stack = vec![fst.root().addr()];
+        while let Some(addr) = stack.pop() {
+            if visited.insert(addr) {
+                stack.extend(fst.node(addr).transitions().map(|t| t.addr));
+            }
+        }
+
+        Self {
+            nodes: visited.len(),
+            bytes: fst.as_bytes().len(),
+            len: fst.len(),
+        }
+    }
+}
+
+/// Statistics about the values of a map.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueStats {
+    /// Number of values.
+    pub count: usize,
+    /// Sum of the values.
+    pub sum: u128,
+    /// Smallest value.
+    pub min: u64,
+    /// Largest value.
+    pub max: u64,
+    /// Arithmetic mean of the values.
+    pub mean: f64,
+}
+
+impl ValueStats {
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "an approximation is good enough"
+    )]
+    pub(crate) fn new(values: impl Iterator<Item = u64>) -> Option<Self> {
+        let (count, sum, min, max) =
+            values.fold((0, 0, u64::MAX, u64::MIN), |acc, value| {
+                (
+                    acc.0 + 1,
+                    acc.1 + u128::from(value),
+                    acc.2.min(value),
+                    acc.3.max(value),
+                )
+            });
+
+        (count != 0).then(|| Self {
+            count,
+            sum,
+            min,
+            max,
+            mean: sum as f64 / count as f64,
+        })
+    }
+}
+
+/// Summary of the spatial spread of a set.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Spread {
+    /// Number of distinct base cells touched by the set.
+    pub base_cells: usize,
+    /// Coarsest resolution in the set.
+    pub min_res: Resolution,
+    /// Finest resolution in the set.
+    pub max_res: Resolution,
+}
+
+impl Spread {
+    pub(crate) fn new<D: AsRef<[u8]>>(set: &Set<D>) -> Option<Self> {
+        let mut stream = set.stream();
+        let mut base_cells = 0;
+        let mut last_base_cell = None;
+        let mut range = ResolutionRange::default();
+        while let Some(key) = stream.next() {
+            // Keys are ordered by base cell first.
+            if last_base_cell != Some(key[0]) {
+                last_base_cell = Some(key[0]);
+                base_cells += 1;
+            }
+            range.push(key);
+        }
+
+        range.finish().map(|(min_res, max_res)| Self {
+            base_cells,
+            min_res,
+            max_res,
+        })
+    }
+}
+
+// ------------------------------------------------------------------------------
+
+/// Accumulates the range of resolutions of a stream of keys.
+#[derive(Default)]
+pub struct ResolutionRange(Option<(usize, usize)>);
+
+impl ResolutionRange {
+    pub fn push(&mut self, key: &[u8]) {
+        // One byte for the base cell, then one per resolution.
+        let len = key.len() - 1;
+        self.0 = Some(
+            self.0
+                .map_or((len, len), |(min, max)| (min.min(len), max.max(len))),
+        );
+    }
+
+    /// Returns the coarsest and finest resolutions, if any key was pushed.
+    pub fn finish(self) -> Option<(Resolution, Resolution)> {
+        self.0.map(|(min, max)| {
+            let resolution = |len| {
+                u8::try_from(len)
+                    .ok()
+                    .and_then(|len| Resolution::try_from(len).ok())
+                    .expect("valid resolution")
+            };
+            (resolution(min), resolution(max))
+        })
+    }
+}
@@ -0,0 +1,120 @@
+/// A value that can be stored in a `FrozenMap`.
+///
+/// Maps store `u64` under the hood, this trait defines a lossless conversion
+/// to and from it.
+///
+/// Note that, while every supported type round-trips (including float NaN
+/// payloads), the ordering of the encoded values doesn't match the ordering
+/// of signed integers or floats: negative numbers are encoded as large
+/// integers. Keep that in mind when relying on ordered values (e.g. the
+/// `merge` of `FrozenMap::rollup` or `FrozenMap::value_bounds_range`).
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait MapValue: sealed::Sealed + Copy {
+    /// Encodes the value as a `u64`.
+    fn to_u64(self) -> u64;
+
+    /// Decodes the value from a `u64`, as encoded by `to_u64`.
+    fn from_u64(value: u64) -> Self;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_unsigned {
+    ($($ty:ty),+) => {$(
+        impl sealed::Sealed for $ty {}
+
+        impl MapValue for $ty {
+            fn to_u64(self) -> u64 {
+                self.into()
+            }
+
+            #[expect(
+                clippy::cast_possible_truncation,
+                reason = "value was encoded from this type"
+            )]
+            fn from_u64(value: u64) -> Self {
+                value as Self
+            }
+        }
+    )+};
+}
+
+impl_unsigned!(u8, u16, u32);
+
+impl sealed::Sealed for u64 {}
+
+impl MapValue for u64 {
+    fn to_u64(self) -> u64 {
+        self
+    }
+
+    fn from_u64(value: u64) -> Self {
+        value
+    }
+}
+
+impl sealed::Sealed for i32 {}
+
+impl MapValue for i32 {
+    #[expect(clippy::cast_sign_loss, reason = "keep the two's complement bits")]
+    fn to_u64(self) -> u64 {
+        u64::from(self as u32)
+    }
+
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        reason = "value was encoded from this type"
+    )]
+    fn from_u64(value: u64) -> Self {
+        value as u32 as Self
+    }
+}
+
+impl sealed::Sealed for i64 {}
+
+impl MapValue for i64 {
+    #[expect(clippy::cast_sign_loss, reason = "keep the two's complement bits")]
+    fn to_u64(self) -> u64 {
+        self as u64
+    }
+
+    #[expect(
+        clippy::cast_possible_wrap,
+        reason = "value was encoded from this type"
+    )]
+    fn from_u64(value: u64) -> Self {
+        value as Self
+    }
+}
+
+impl sealed::Sealed for f32 {}
+
+impl MapValue for f32 {
+    fn to_u64(self) -> u64 {
+        self.to_bits().into()
+    }
+
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "value was encoded from this type"
+    )]
+    fn from_u64(value: u64) -> Self {
+        Self::from_bits(value as u32)
+    }
+}
+
+impl sealed::Sealed for f64 {}
+
+impl MapValue for f64 {
+    fn to_u64(self) -> u64 {
+        self.to_bits()
+    }
+
+    fn from_u64(value: u64) -> Self {
+        Self::from_bits(value)
+    }
+}
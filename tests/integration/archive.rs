@@ -0,0 +1,121 @@
+use crate::cell_index;
+use h3o::Resolution;
+use h3o_ice::{Archive, ArchiveBuilder, BuildError, FrozenSet};
+
+#[test]
+fn roundtrip() {
+    let sets = [
+        (
+            "paris",
+            FrozenSet::try_from_iter(std::iter::once(cell_index!(
+                0x8a1fb46622dffff
+            )))
+            .expect("failed to create set"),
+        ),
+        (
+            "shanghai",
+            FrozenSet::try_from_iter(
+                cell_index!(0x85318d83fffffff).children(Resolution::Seven),
+            )
+            .expect("failed to create set"),
+        ),
+        ("empty", FrozenSet::default()),
+    ];
+
+    let mut builder = ArchiveBuilder::new(Vec::new());
+    for &(name, ref set) in &sets {
+        builder.add_set(name, set);
+    }
+    let bytes = builder.finish().expect("failed to write archive");
+
+    let archive = Archive::open(bytes).expect("failed to open archive");
+    assert_eq!(
+        archive.names().collect::<Vec<_>>(),
+        vec!["paris", "shanghai", "empty"],
+        "names"
+    );
+    for &(name, ref set) in &sets {
+        let result = archive
+            .get_set(name)
+            .expect("valid set")
+            .expect("archived set");
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            set.iter().collect::<Vec<_>>(),
+            "{name}"
+        );
+    }
+    assert!(
+        archive.get_set("london").expect("no set").is_none(),
+        "unknown name"
+    );
+}
+
+#[test]
+fn invalid() {
+    let mut builder = ArchiveBuilder::new(Vec::new());
+    builder.add_set("empty", &FrozenSet::default());
+    let bytes = builder.finish().expect("failed to write archive");
+
+    let result = Archive::open(&bytes[..bytes.len() - 1]);
+    assert!(
+        matches!(result, Err(BuildError::InvalidArchive)),
+        "truncated"
+    );
+}
+
+#[test]
+fn header() {
+    let mut builder = ArchiveBuilder::new(Vec::new());
+    builder.add_set("empty", &FrozenSet::default());
+    let bytes = builder.finish().expect("failed to write archive");
+
+    let mut invalid = bytes.clone();
+    invalid[0] = b'X';
+    let result = Archive::open(invalid);
+    assert!(
+        matches!(result, Err(BuildError::InvalidArchive)),
+        "bad magic number"
+    );
+
+    let mut invalid = bytes;
+    invalid[8] = 42;
+    let result = Archive::open(invalid);
+    assert!(
+        matches!(
+            result,
+            Err(BuildError::VersionMismatch {
+                expected: 1,
+                found: 42
+            })
+        ),
+        "unsupported version"
+    );
+}
+
+#[test]
+fn corrupted_set() {
+    let set = FrozenSet::try_from_iter(
+        cell_index!(0x85318d83fffffff).children(Resolution::Seven),
+    )
+    .expect("failed to create set");
+    let mut builder = ArchiveBuilder::new(Vec::new());
+    builder.add_set("shanghai", &set);
+    let mut bytes = builder.finish().expect("failed to write archive");
+
+    // The set is stored at the end of the archive.
+    let start = bytes.len() - set.as_bytes().len();
+    bytes[start..].fill(0);
+    let archive = Archive::open(bytes).expect("failed to open archive");
+    assert!(archive.get_set("shanghai").is_err(), "corrupted set");
+}
+
+#[test]
+fn write_error() {
+    let mut buffer = [0; 4];
+    let mut builder = ArchiveBuilder::new(&mut buffer[..]);
+    builder.add_set("empty", &FrozenSet::default());
+
+    let result = builder.finish();
+    assert!(matches!(result, Err(BuildError::Io(_))), "buffer too small");
+}
@@ -1,6 +1,6 @@
 use crate::cell_index;
-use h3o::{CellIndex, Resolution};
-use h3o_ice::{FrozenMap, FrozenMapBuilder};
+use h3o::{CellIndex, LatLng, Resolution};
+use h3o_ice::{FrozenMap, FrozenMapBuilder, MapValue};
 use std::{error::Error, io::Cursor, ops::Bound};
 
 #[test]
@@ -21,6 +21,80 @@ fn len() {
     assert_eq!(multiple.len(), 49, "multiple elements");
 }
 
+#[test]
+fn iter_filter() {
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+
+    let result = map.iter_filter(|value| value > 40).collect::<Vec<_>>();
+    let expected = test_cells()
+        .filter(|&(_, value)| value > 40)
+        .collect::<Vec<_>>();
+    assert_eq!(result, expected, "matching entries");
+
+    assert_eq!(map.iter_filter(|_| false).count(), 0, "no match");
+    assert_eq!(map.iter_filter(|_| true).count(), 49, "every entry");
+}
+
+#[test]
+fn values_columnar() {
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+
+    let result = map.values_columnar();
+    assert_eq!(result, map.values().collect::<Vec<_>>(), "key order");
+}
+
+#[test]
+fn to_columnar() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+    let columnar = map.to_columnar().expect("failed to create columnar map");
+
+    assert_eq!(columnar.len(), map.len(), "length");
+    assert_eq!(columnar.values(), map.values_columnar(), "values");
+    for (cell, _) in test_cells() {
+        assert_eq!(columnar.get(cell), map.get(cell), "exact match");
+        let child = cell.center_child(Resolution::Ten).expect("center child");
+        assert_eq!(columnar.get(child), map.get(child), "ancestor match");
+    }
+    assert_eq!(columnar.get(parent), None, "missing");
+}
+
+#[test]
+fn to_vec() {
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+    let entries = map.to_vec();
+
+    assert_eq!(entries.len(), map.len(), "length");
+    assert_eq!(entries, map.iter().collect::<Vec<_>>(), "content");
+}
+
+#[test]
+fn write_jsonl() {
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+    let mut buffer = Vec::new();
+    map.write_jsonl(&mut buffer).expect("failed to write JSONL");
+    let output = String::from_utf8(buffer).expect("valid UTF-8");
+    let lines = output.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), map.len(), "one line per entry");
+    assert_eq!(
+        lines.first(),
+        Some(&r#"{"cell":"87318d800ffffff","value":0}"#),
+        "first line"
+    );
+    let (cell, value) = test_cells().last().expect("non-empty test data");
+    assert_eq!(
+        lines.last().copied(),
+        Some(format!(r#"{{"cell":"{cell}","value":{value}}}"#).as_str()),
+        "last line"
+    );
+}
+
 #[test]
 fn is_empty() {
     let empty = FrozenMap::try_from_iter(std::iter::empty())
@@ -35,6 +109,12 @@ fn is_empty() {
     assert!(!single.is_empty(), "single element");
 }
 
+#[test]
+fn default() {
+    let map = FrozenMap::default();
+    assert!(map.is_empty(), "empty map");
+}
+
 #[test]
 fn contains_key() {
     let cell = cell_index!(0x8a1fb46622dffff);
@@ -71,6 +151,142 @@ fn get() {
     assert!(map.get(not_related).is_none(), "not related");
 }
 
+#[test]
+fn get_base_cell() {
+    let base_cell = cell_index!(0x801ffffffffffff);
+    let cell = cell_index!(0x8a1fb46622dffff);
+    let descendant = cell_index!(0x8d1fb46622d85bf);
+    let map = FrozenMap::try_from_iter(vec![(base_cell, 7), (cell, 33)])
+        .expect("failed to create map");
+
+    assert_eq!(map.get(base_cell), Some((base_cell, 7)), "exact match");
+    // The coarsest match wins.
+    assert_eq!(map.get(descendant), Some((base_cell, 7)), "descendant");
+    assert_eq!(
+        map.descendants(base_cell).collect::<Vec<_>>(),
+        vec![(cell, 33)],
+        "descendants"
+    );
+
+    let not_related = cell_index!(0x8029fffffffffff);
+    assert!(map.get(not_related).is_none(), "other base cell");
+}
+
+#[test]
+fn get_or() {
+    let cell = cell_index!(0x8a1fb46622dffff);
+    let child = cell_index!(0x8b1fb46622d8fff);
+    let map = FrozenMap::try_from_iter(std::iter::once((cell, 33)))
+        .expect("failed to create map");
+
+    assert_eq!(map.get_or(child, 7), 33, "ancestor");
+    assert_eq!(map.get_exact_or(child, 7), 7, "no exact match");
+    assert_eq!(map.get_exact_or(cell, 7), 33, "exact match");
+
+    let not_related = cell_index!(0x85283473fffffff);
+    assert_eq!(map.get_or(not_related, 7), 7, "not related");
+}
+
+#[test]
+fn get_path_values() {
+    let cell = cell_index!(0x8a1fb46622dffff);
+    let parent = cell.parent(Resolution::Five).expect("parent");
+    let grand_parent = cell.parent(Resolution::Three).expect("grand-parent");
+    let descendant = cell_index!(0x8d1fb46622d85bf);
+    let map = FrozenMap::try_from_iter(vec![
+        (grand_parent, 1024),
+        (parent, 512),
+        (cell, 33),
+    ])
+    .expect("failed to create map");
+
+    assert_eq!(
+        map.get_path_values(descendant),
+        vec![(grand_parent, 1024), (parent, 512), (cell, 33)],
+        "full path"
+    );
+    assert_eq!(
+        map.get_path_values(parent),
+        vec![(grand_parent, 1024), (parent, 512)],
+        "partial path"
+    );
+    // `get` stops at the coarsest match.
+    assert_eq!(map.get(descendant), Some((grand_parent, 1024)), "get");
+
+    let not_related = cell_index!(0x85283473fffffff);
+    assert!(map.get_path_values(not_related).is_empty(), "not related");
+}
+
+#[test]
+fn get_value() {
+    fn roundtrip<V: MapValue>(value: V) -> V {
+        let cell = cell_index!(0x8a1fb46622dffff);
+        let mut builder = FrozenMapBuilder::memory();
+        builder.insert_value(cell, value).expect("insert");
+        let map = builder.into_map();
+        let (matched, result) = map.get_value::<V>(cell).expect("value");
+        assert_eq!(matched, cell);
+        result
+    }
+
+    assert_eq!(roundtrip(u8::MAX), u8::MAX, "u8");
+    assert_eq!(roundtrip(u16::MAX), u16::MAX, "u16");
+    assert_eq!(roundtrip(u32::MAX), u32::MAX, "u32");
+    assert_eq!(roundtrip(u64::MAX), u64::MAX, "u64");
+    assert_eq!(roundtrip(i32::MIN), i32::MIN, "i32 min");
+    assert_eq!(roundtrip(-1_i32), -1, "i32");
+    assert_eq!(roundtrip(i64::MIN), i64::MIN, "i64 min");
+    assert_eq!(roundtrip(-1_i64), -1, "i64");
+    assert_eq!(roundtrip(-0.5_f32).to_bits(), (-0.5_f32).to_bits(), "f32");
+    assert_eq!(roundtrip(1e300_f64).to_bits(), 1e300_f64.to_bits(), "f64");
+    assert!(roundtrip(f32::NAN).is_nan(), "f32 NaN");
+    assert!(roundtrip(f64::NAN).is_nan(), "f64 NaN");
+    assert_eq!(
+        roundtrip(f64::NEG_INFINITY),
+        f64::NEG_INFINITY,
+        "f64 infinity"
+    );
+}
+
+#[test]
+fn get_slice() {
+    let cells = [
+        cell_index!(0x8a1fb46622dffff),
+        cell_index!(0x85283473fffffff),
+        cell_index!(0x85318d83fffffff),
+    ];
+    let mut blob = Vec::new();
+    let mut builder = FrozenMapBuilder::memory();
+    for (cell, payload) in cells.into_iter().zip([b"foo", b"bar", b"baz"]) {
+        builder
+            .insert_with_offset(cell, payload, &mut blob)
+            .expect("failed to insert");
+    }
+    let map = builder.into_map();
+
+    assert_eq!(
+        map.get_slice(cells[1], &blob, 3),
+        Some(&b"bar"[..]),
+        "middle"
+    );
+    assert_eq!(map.get_slice(cells[2], &blob, 4), None, "out of bounds");
+    let not_related = cell_index!(0x8aa88b946a27fff);
+    assert_eq!(map.get_slice(not_related, &blob, 3), None, "not related");
+}
+
+#[test]
+fn get_exact() {
+    let cell = cell_index!(0x8a1fb46622dffff);
+    let ancestor = cell_index!(0x85283473fffffff);
+    let child = cell_index!(0x8a2834701ab7fff);
+    let map = FrozenMap::try_from_iter(vec![(cell, 33), (ancestor, 1024)])
+        .expect("failed to create map");
+
+    assert_eq!(map.get_exact(cell), Some(33), "exact match");
+    assert_eq!(map.get_exact(ancestor), Some(1024), "exact match");
+    assert!(map.get_exact(child).is_none(), "descendant");
+}
+
 #[test]
 fn load_from_bytes() {
     // Build map in memory.
@@ -94,6 +310,76 @@ fn load_from_bytes() {
     );
 }
 
+#[test]
+fn merge_from_bytes() {
+    let shared = cell_index!(0x85318d83fffffff);
+    let map1 = FrozenMap::try_from_iter(vec![
+        (cell_index!(0x85283473fffffff), 1),
+        (shared, 10),
+    ])
+    .expect("failed to create map");
+    let map2 = FrozenMap::try_from_iter(vec![
+        (shared, 32),
+        (cell_index!(0x8aa88b946a27fff), 5),
+    ])
+    .expect("failed to create map");
+    let buffers = [map1.as_bytes().to_vec(), map2.as_bytes().to_vec()];
+
+    let result = FrozenMap::merge_from_bytes(&buffers, |a, b| a + b)
+        .expect("failed to merge maps");
+
+    let expected = vec![
+        (cell_index!(0x85283473fffffff), 1),
+        (shared, 42),
+        (cell_index!(0x8aa88b946a27fff), 5),
+    ];
+    assert_eq!(result.iter().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn to_owned() {
+    let bytes = FrozenMap::try_from_iter(test_cells())
+        .expect("failed to create map")
+        .as_bytes()
+        .to_vec();
+
+    let owned = {
+        let borrowed = FrozenMap::new(bytes.as_slice()).expect("valid map");
+        borrowed.to_owned()
+    };
+    drop(bytes);
+
+    assert_eq!(
+        owned.iter().collect::<Vec<_>>(),
+        test_cells().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn try_from_latlng_values() {
+    let a = LatLng::new(48.8566, 2.3522).expect("valid point");
+    let b = LatLng::new(48.8567, 2.3523).expect("valid point");
+    let c = LatLng::new(45.7640, 4.8357).expect("valid point");
+    let map = FrozenMap::try_from_latlng_values(
+        [(a, 1), (c, 10), (b, 100)],
+        Resolution::Nine,
+        |x, y| x + y,
+    )
+    .expect("failed to create map");
+
+    assert_eq!(map.len(), 2, "two cells");
+    assert_eq!(
+        map.get_exact(a.to_cell(Resolution::Nine)),
+        Some(101),
+        "merged"
+    );
+    assert_eq!(
+        map.get_exact(c.to_cell(Resolution::Nine)),
+        Some(10),
+        "alone"
+    );
+}
+
 #[test]
 fn io_build() {
     let buffer = Cursor::new(Vec::new());
@@ -105,6 +391,68 @@ fn io_build() {
     builder.finish().expect("flushing map");
 }
 
+#[test]
+fn into_map_and_bytes() {
+    let mut builder = FrozenMapBuilder::memory();
+    builder.extend_iter(test_cells()).expect("failed to extend");
+    let (map, bytes) = builder.into_map_and_bytes();
+
+    let reloaded = FrozenMap::new(bytes.to_vec()).expect("failed to reload");
+    assert_eq!(
+        reloaded.iter().collect::<Vec<_>>(),
+        map.iter().collect::<Vec<_>>()
+    );
+    assert_eq!(map.len(), 49);
+
+    let shared = bytes.clone();
+    assert_eq!(shared.as_ptr(), bytes.as_ptr(), "shared buffer");
+}
+
+#[test]
+fn insert_nonzero() {
+    let mut builder = FrozenMapBuilder::memory();
+    for (cell, value) in test_cells() {
+        builder
+            .insert_nonzero(cell, value % 3)
+            .expect("failed to insert");
+    }
+    let map = builder.into_map();
+
+    let result = map.keys().collect::<Vec<_>>();
+    let expected = test_cells()
+        .filter_map(|(cell, value)| (value % 3 != 0).then_some(cell))
+        .collect::<Vec<_>>();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn coarsen_to() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let mut builder =
+        FrozenMapBuilder::memory().coarsen_to(Resolution::Seven, |a, b| a + b);
+    // Coarser cells are inserted as is.
+    builder.insert(parent, 1000).expect("failed to insert");
+    builder
+        .extend_iter(parent.children(Resolution::Ten).map(|cell| (cell, 1)))
+        .expect("failed to insert");
+    let map = builder.into_map().expect("failed to create map");
+
+    let result = map.iter().collect::<Vec<_>>();
+    let expected = std::iter::once((parent, 1000))
+        .chain(parent.children(Resolution::Seven).map(|cell| (cell, 343)))
+        .collect::<Vec<_>>();
+    assert_eq!(result, expected);
+
+    let mut builder =
+        FrozenMapBuilder::memory().coarsen_to(Resolution::Seven, u64::max);
+    let result = builder.extend_iter([
+        (cell_index!(0x85318d83fffffff), 1),
+        (cell_index!(0x85283473fffffff), 2),
+        (cell_index!(0x8a1fb46622dffff), 3),
+    ]);
+    assert!(result.is_err(), "out of order");
+}
+
 #[test]
 fn keys() {
     let map =
@@ -115,6 +463,16 @@ fn keys() {
     assert_eq!(result, expected);
 }
 
+#[test]
+fn keys_u64() {
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+    let result = map.keys_u64().collect::<Vec<_>>();
+    let expected = map.keys().map(u64::from).collect::<Vec<_>>();
+
+    assert_eq!(result, expected);
+}
+
 #[test]
 fn values() {
     let map =
@@ -125,6 +483,177 @@ fn values() {
     assert_eq!(result, expected);
 }
 
+#[test]
+fn iter_boundaries() {
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+    let (cell, boundary, value) =
+        map.iter_boundaries().next().expect("first cell");
+
+    assert_eq!(cell, cell_index!(0x87318d800ffffff), "cell");
+    assert_eq!(boundary.len(), 6, "hexagon");
+    assert_eq!(value, 0, "value");
+}
+
+#[test]
+fn agrees_with() {
+    let cells = test_cells().collect::<Vec<_>>();
+    let map = FrozenMap::try_from_iter(cells.iter().copied())
+        .expect("failed to create map");
+
+    // Shared keys with the same values, plus keys only on one side.
+    let other = FrozenMap::try_from_iter(
+        cells[10..]
+            .iter()
+            .copied()
+            .chain(std::iter::once((cell_index!(0x85318d93fffffff), 7))),
+    )
+    .expect("failed to create map");
+    assert!(map.agrees_with(&other), "agreement");
+    assert!(other.agrees_with(&map), "symmetric");
+
+    let other = FrozenMap::try_from_iter(
+        cells[10..]
+            .iter()
+            .enumerate()
+            .map(|(idx, &(cell, value))| (cell, value + u64::from(idx == 5))),
+    )
+    .expect("failed to create map");
+    assert!(!map.agrees_with(&other), "mismatch");
+
+    let empty = FrozenMap::try_from_iter(std::iter::empty())
+        .expect("failed to create map");
+    assert!(map.agrees_with(&empty), "nothing shared");
+}
+
+#[test]
+fn value_diff() {
+    let cells = cell_index!(0x85318d83fffffff)
+        .children(Resolution::Six)
+        .collect::<Vec<_>>();
+    let left =
+        FrozenMap::try_from_iter([(cells[0], 1), (cells[1], 2), (cells[2], 3)])
+            .expect("failed to create map");
+    let right =
+        FrozenMap::try_from_iter([(cells[1], 2), (cells[2], 4), (cells[3], 5)])
+            .expect("failed to create map");
+
+    let result = left.value_diff(&right).collect::<Vec<_>>();
+    let expected = vec![
+        (cells[0], Some(1), None),
+        (cells[2], Some(3), Some(4)),
+        (cells[3], None, Some(5)),
+    ];
+    assert_eq!(result, expected, "differences");
+
+    assert_eq!(left.value_diff(&left).count(), 0, "identical");
+}
+
+#[test]
+fn map_values() {
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+
+    let result = map.map_values(|value| value * 2).expect("map values");
+    let expected = test_cells()
+        .map(|(cell, value)| (cell, value * 2))
+        .collect::<Vec<_>>();
+    assert_eq!(result.iter().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn dense_remap() {
+    // Sparse values: 7, 1007 and 2007.
+    let sparse =
+        || test_cells().map(|(cell, idx)| (cell, (idx % 3) * 1000 + 7));
+    let map = FrozenMap::try_from_iter(sparse()).expect("failed to create map");
+
+    let (result, table) = map.dense_remap().expect("dense remap");
+    assert_eq!(table, vec![7, 1007, 2007], "lookup table");
+
+    let mut values = result.values().collect::<Vec<_>>();
+    values.sort_unstable();
+    values.dedup();
+    assert_eq!(values, vec![0, 1, 2], "contiguous");
+
+    let reversed = result
+        .iter()
+        .map(|(cell, value)| (cell, table[value as usize]))
+        .collect::<Vec<_>>();
+    assert_eq!(reversed, sparse().collect::<Vec<_>>(), "reversible");
+}
+
+#[test]
+fn rollup() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let coarse = cell_index!(0x8a1fb46622dffff)
+        .parent(Resolution::Four)
+        .expect("coarse cell");
+    let map = FrozenMap::try_from_iter(
+        std::iter::once((coarse, 1000)).chain(test_cells()),
+    )
+    .expect("failed to create map");
+
+    let result = map
+        .rollup(Resolution::Five, |a, b| a + b)
+        .expect("failed to rollup");
+    let expected = vec![
+        (coarse, 1000),
+        (parent, test_cells().map(|(_, value)| value).sum()),
+    ];
+    assert_eq!(result.iter().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn downsample() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+
+    let result = map
+        .downsample_max(Resolution::Five)
+        .expect("failed to downsample");
+    assert_eq!(result.iter().collect::<Vec<_>>(), vec![(parent, 48)], "max");
+
+    let result = map
+        .downsample_min(Resolution::Five)
+        .expect("failed to downsample");
+    assert_eq!(result.iter().collect::<Vec<_>>(), vec![(parent, 0)], "min");
+
+    let result = map
+        .downsample_sum(Resolution::Five)
+        .expect("failed to downsample");
+    assert_eq!(
+        result.iter().collect::<Vec<_>>(),
+        vec![(parent, 1176)],
+        "sum"
+    );
+}
+
+#[test]
+fn pyramid() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+
+    let levels = map
+        .pyramid(&[Resolution::Six, Resolution::Five], u64::max)
+        .expect("failed to build pyramid");
+    assert_eq!(levels.len(), 2, "one map per level");
+
+    // Grandchildren are numbered in order, 7 per child.
+    let expected = parent
+        .children(Resolution::Six)
+        .zip((0..7).map(|idx| idx * 7 + 6))
+        .collect::<Vec<_>>();
+    assert_eq!(levels[0].iter().collect::<Vec<_>>(), expected, "fine level");
+    assert_eq!(
+        levels[1].iter().collect::<Vec<_>>(),
+        vec![(parent, 48)],
+        "coarse level"
+    );
+}
+
 #[test]
 fn wrong_order() {
     // Building map from non-sorted input fails.
@@ -250,6 +779,78 @@ fn range() {
     assert_eq!(result, expected, "RangeToInclusive");
 }
 
+#[test]
+fn value_bounds_range() {
+    let map = FrozenMap::try_from_iter(
+        cell_index!(0x85318d83fffffff)
+            .children(Resolution::Six)
+            .enumerate()
+            .map(|(idx, cell)| (cell, idx as u64)),
+    )
+    .expect("failed to create map");
+
+    let result = map.value_bounds_range((
+        Bound::Included(cell_index!(0x86318d817ffffff)),
+        Bound::Included(cell_index!(0x86318d827ffffff)),
+    ));
+    assert_eq!(result, Some((2, 4)), "bounded range");
+
+    let result = map
+        .value_bounds_range((Bound::<CellIndex>::Unbounded, Bound::Unbounded));
+    assert_eq!(result, Some((0, 6)), "full range");
+
+    let result = map.value_bounds_range((
+        Bound::Excluded(cell_index!(0x86318d817ffffff)),
+        Bound::Excluded(cell_index!(0x86318d81fffffff)),
+    ));
+    assert_eq!(result, None, "empty range");
+}
+
+#[test]
+fn iter_from() {
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+
+    // Page through the map, 10 by 10.
+    let mut result = Vec::new();
+    let mut last = None;
+    loop {
+        let page = match last {
+            Some(cell) => map.iter_from(cell).skip(1).take(10).collect(),
+            None => map.iter().take(10).collect::<Vec<_>>(),
+        };
+        if page.is_empty() {
+            break;
+        }
+        last = page.last().map(|&(cell, _)| cell);
+        result.extend(page);
+    }
+    let expected = test_cells().collect::<Vec<_>>();
+    assert_eq!(result, expected, "no gap nor duplicate");
+
+    let beyond = cell_index!(0x85318d93fffffff);
+    assert_eq!(map.iter_from(beyond).count(), 0, "beyond the last key");
+}
+
+#[test]
+fn range_fused() {
+    let map = FrozenMap::try_from_iter(
+        cell_index!(0x85318d83fffffff)
+            .children(Resolution::Six)
+            .enumerate()
+            .map(|(idx, cell)| (cell, idx as u64)),
+    )
+    .expect("failed to create map");
+
+    let mut iter = map.range((
+        Bound::Included(cell_index!(0x86318d817ffffff)),
+        Bound::Excluded(cell_index!(0x86318d81fffffff)),
+    ));
+    assert!(iter.next().is_some(), "first item");
+    assert!(iter.next().is_none(), "exhausted");
+    assert!(iter.next().is_none(), "still exhausted");
+}
+
 #[test]
 fn descendants() {
     let map = FrozenMap::try_from_iter(
@@ -277,6 +878,120 @@ fn descendants() {
     assert_eq!(result, 14, "grand-parent");
 }
 
+#[test]
+fn descendants_res14() {
+    let parent = cell_index!(0x8e2a1072b598647);
+    let map =
+        FrozenMap::try_from_iter(parent.children(Resolution::Fifteen).zip(0..))
+            .expect("failed to create map");
+
+    let result = map.descendants(parent).collect::<Vec<_>>();
+    let expected = parent
+        .children(Resolution::Fifteen)
+        .zip(0..)
+        .collect::<Vec<_>>();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn children_values() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let children = parent.children(Resolution::Six).collect::<Vec<_>>();
+    // Even children are present, the second one only has grandchildren.
+    let mut entries = Vec::new();
+    for (idx, &cell) in children.iter().enumerate() {
+        if idx % 2 == 0 {
+            entries.push((cell, idx as u64));
+        } else if idx == 1 {
+            entries.extend(cell.children(Resolution::Seven).map(|c| (c, 100)));
+        }
+    }
+    let map = FrozenMap::try_from_iter(entries).expect("failed to create map");
+
+    let result = map.children_values(parent);
+    let expected = children
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, &cell)| (idx % 2 == 0).then_some((cell, idx as u64)))
+        .collect::<Vec<_>>();
+    assert_eq!(result, expected);
+
+    let result = map.children_values(cell_index!(0x8f318d831000000));
+    assert!(result.is_empty(), "no children");
+}
+
+#[test]
+fn interpolate() {
+    let center = cell_index!(0x8a1fb46622dffff);
+    let mut disk = center.grid_disk_distances::<Vec<_>>(2);
+    disk.sort_unstable();
+    // The center is empty, ring 1 has 3 values and ring 2 has one.
+    let mut ring1 = 0;
+    let entries = disk
+        .iter()
+        .filter_map(|&(cell, distance)| match distance {
+            1 if ring1 < 3 => {
+                ring1 += 1;
+                Some((cell, ring1 * 10))
+            }
+            2 if ring1 == 3 => {
+                ring1 += 1;
+                Some((cell, 100))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let map = FrozenMap::try_from_iter(entries).expect("failed to create map");
+
+    assert_eq!(map.interpolate(center, 0), None, "no value");
+    assert_eq!(map.interpolate(center, 1), Some(20.), "ring 1");
+    assert_eq!(map.interpolate(center, 2), Some(40.), "ring 2");
+
+    let result = map.interpolate_weighted(center, 2, |distance| {
+        if distance == 1 {
+            1.
+        } else {
+            3.
+        }
+    });
+    assert_eq!(result, Some(60.), "weighted");
+}
+
+#[test]
+fn value_stats() {
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+    let stats = map.value_stats().expect("non-empty map");
+
+    assert_eq!(stats.count, 49, "count");
+    assert_eq!(stats.sum, 1176, "sum");
+    assert_eq!(stats.min, 0, "min");
+    assert_eq!(stats.max, 48, "max");
+    assert_eq!(stats.mean, 24., "mean");
+
+    let map = FrozenMap::try_from_iter(std::iter::empty())
+        .expect("failed to create map");
+    assert!(map.value_stats().is_none(), "empty");
+}
+
+#[test]
+fn value_histogram() {
+    let map =
+        FrozenMap::try_from_iter(test_cells()).expect("failed to create map");
+
+    // Values are 0 to 48.
+    let result = map.value_histogram(&[10, 20, 40]);
+    assert_eq!(result, Some(vec![10, 10, 20, 9]), "buckets and overflow");
+
+    let result = map.value_histogram(&[]);
+    assert_eq!(result, Some(vec![49]), "overflow only");
+
+    let result = map.value_histogram(&[0, 48, 49]);
+    assert_eq!(result, Some(vec![0, 48, 1, 0]), "edges");
+
+    assert!(map.value_histogram(&[20, 10]).is_none(), "unsorted buckets");
+}
+
 // -----------------------------------------------------------------------------
 
 fn test_cells() -> impl Iterator<Item = (h3o::CellIndex, u64)> {
@@ -1,4 +1,6 @@
+mod archive;
 mod map;
+mod ops;
 mod set;
 
 #[macro_export]
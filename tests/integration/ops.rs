@@ -0,0 +1,69 @@
+use crate::cell_index;
+use h3o::{LatLng, Resolution};
+use h3o_ice::{DiffTag, FrozenMap, FrozenSet};
+
+#[test]
+fn zip_map() {
+    let set = FrozenSet::try_from_iter(
+        cell_index!(0x85318d83fffffff).children(Resolution::Six),
+    )
+    .expect("failed to create set");
+    let map = FrozenMap::try_from_iter(vec![
+        (cell_index!(0x86318d80fffffff), 1),
+        (cell_index!(0x86318d827ffffff), 4),
+    ])
+    .expect("failed to create map");
+
+    let result = h3o_ice::zip_map(&set, &map).collect::<Vec<_>>();
+    let expected = vec![
+        (cell_index!(0x86318d807ffffff), None),
+        (cell_index!(0x86318d80fffffff), Some(1)),
+        (cell_index!(0x86318d817ffffff), None),
+        (cell_index!(0x86318d81fffffff), None),
+        (cell_index!(0x86318d827ffffff), Some(4)),
+        (cell_index!(0x86318d82fffffff), None),
+        (cell_index!(0x86318d837ffffff), None),
+    ];
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn diff() {
+    let children = cell_index!(0x85318d83fffffff)
+        .children(Resolution::Six)
+        .collect::<Vec<_>>();
+    let a = FrozenSet::try_from_iter(children[..4].iter().copied())
+        .expect("failed to create set");
+    let b = FrozenSet::try_from_iter(children[2..].iter().copied())
+        .expect("failed to create set");
+
+    let result = h3o_ice::diff(&a, &b).collect::<Vec<_>>();
+    let expected = vec![
+        (children[0], DiffTag::OnlyLeft),
+        (children[1], DiffTag::OnlyLeft),
+        (children[2], DiffTag::Both),
+        (children[3], DiffTag::Both),
+        (children[4], DiffTag::OnlyRight),
+        (children[5], DiffTag::OnlyRight),
+        (children[6], DiffTag::OnlyRight),
+    ];
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn bin_points() {
+    let points = [
+        LatLng::new(48.8566, 2.3522).expect("valid coordinate"),
+        LatLng::new(45.7640, 4.8357).expect("valid coordinate"),
+        LatLng::new(48.8567, 2.3523).expect("valid coordinate"),
+    ];
+    let map = h3o_ice::bin_points(points, Resolution::Nine)
+        .expect("failed to create map");
+
+    let paris = points[0].to_cell(Resolution::Nine);
+    let lyon = points[1].to_cell(Resolution::Nine);
+    assert_eq!(points[2].to_cell(Resolution::Nine), paris, "same cell");
+    assert_eq!(map.len(), 2, "length");
+    assert_eq!(map.get(paris), Some((paris, 2)), "shared cell");
+    assert_eq!(map.get(lyon), Some((lyon, 1)), "single point");
+}
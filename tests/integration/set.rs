@@ -1,7 +1,11 @@
 use crate::cell_index;
-use h3o::{CellIndex, Resolution};
-use h3o_ice::{FrozenSet, FrozenSetBuilder};
-use std::{error::Error, io::Cursor, ops::Bound};
+use h3o::{CellIndex, LatLng, Resolution};
+use h3o_ice::{BuildError, FrozenSet, FrozenSetBuilder};
+use std::{
+    io::Cursor,
+    num::NonZeroUsize,
+    ops::{Bound, ControlFlow},
+};
 
 #[test]
 fn len() {
@@ -20,6 +24,46 @@ fn len() {
     assert_eq!(multiple.len(), 49, "multiple elements");
 }
 
+#[test]
+fn len_large() {
+    let set = FrozenSet::try_from_iter(
+        cell_index!(0x85318d83fffffff).children(Resolution::Ten),
+    )
+    .expect("failed to create set");
+
+    assert_eq!(set.len(), 16_807, "length");
+    assert!(!set.is_empty(), "not empty");
+    assert_eq!(set.iter().len(), set.len(), "consistent with iterator");
+}
+
+#[test]
+fn len_borrowed() {
+    // Like a memory-mapped file, the set doesn't own its bytes.
+    let bytes = FrozenSet::try_from_iter(test_cells())
+        .expect("failed to create set")
+        .as_bytes()
+        .to_vec();
+    let set = FrozenSet::new(bytes.as_slice()).expect("failed to load set");
+    assert_eq!(set.len(), 49, "length");
+    assert!(!set.is_empty(), "not empty");
+    assert_eq!(set.iter().count(), set.len(), "consistent with iterator");
+
+    let bytes = FrozenSet::default().as_bytes().to_vec();
+    let set = FrozenSet::new(bytes.as_slice()).expect("failed to load set");
+    assert_eq!(set.len(), 0, "empty length");
+    assert!(set.is_empty(), "empty");
+}
+
+#[test]
+fn to_vec() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let cells = set.to_vec();
+
+    assert_eq!(cells.len(), set.len(), "length");
+    assert_eq!(cells, set.iter().collect::<Vec<_>>(), "content");
+}
+
 #[test]
 fn is_empty() {
     let empty = FrozenSet::try_from_iter(std::iter::empty())
@@ -33,6 +77,88 @@ fn is_empty() {
     assert!(!single.is_empty(), "single element");
 }
 
+#[test]
+fn default() {
+    let set = FrozenSet::default();
+    assert!(set.is_empty(), "empty set");
+}
+
+#[test]
+fn ord() {
+    let a = FrozenSet::try_from_iter(std::iter::once(cell_index!(
+        0x8a1fb46622dffff
+    )))
+    .expect("failed to create set");
+    let b =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let c = FrozenSet::default();
+
+    let mut sets = vec![a.to_owned(), b.to_owned(), c.to_owned()];
+    sets.sort();
+    let mut reversed = vec![c, b, a];
+    reversed.sort();
+    assert!(sets == reversed, "deterministic order");
+    assert!(
+        sets.windows(2)
+            .all(|pair| pair[0].as_bytes() <= pair[1].as_bytes()),
+        "ordered by bytes"
+    );
+
+    let copy = FrozenSet::new(sets[1].as_bytes().to_vec())
+        .expect("failed to load set");
+    assert!(copy == sets[1], "equality");
+}
+
+#[test]
+fn fst_stats() {
+    let expanded =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let compacted = FrozenSet::try_from_iter(std::iter::once(cell_index!(
+        0x85318d83fffffff
+    )))
+    .expect("failed to create set");
+
+    let expanded_stats = expanded.fst_stats();
+    let compacted_stats = compacted.fst_stats();
+
+    assert_eq!(expanded_stats.len, 49, "expanded length");
+    assert_eq!(compacted_stats.len, 1, "compacted length");
+    assert_eq!(
+        expanded_stats.bytes,
+        expanded.as_bytes().len(),
+        "expanded size"
+    );
+    assert!(compacted_stats.nodes < expanded_stats.nodes, "fewer nodes");
+    assert!(compacted_stats.bytes < expanded_stats.bytes, "fewer bytes");
+}
+
+#[test]
+fn spread() {
+    let set = FrozenSet::try_from_iter([
+        cell_index!(0x8a1fb46622dffff),
+        cell_index!(0x85318d83fffffff),
+        cell_index!(0x85318d93fffffff),
+    ])
+    .expect("failed to create set");
+    let spread = set.spread().expect("non-empty set");
+
+    assert_eq!(spread.base_cells, 2, "base cells");
+    assert_eq!(spread.min_res, Resolution::Five, "min resolution");
+    assert_eq!(spread.max_res, Resolution::Ten, "max resolution");
+
+    assert!(FrozenSet::default().spread().is_none(), "empty set");
+}
+
+#[test]
+fn bytes_per_cell() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let result = set.bytes_per_cell();
+    assert!(result.is_finite() && result > 0., "non-empty set");
+
+    assert!(FrozenSet::default().bytes_per_cell() == 0., "empty set");
+}
+
 #[test]
 fn contains() {
     let cell = cell_index!(0x8a1fb46622dffff);
@@ -52,6 +178,258 @@ fn contains() {
     assert!(set.contains(not_related).is_none(), "not related");
 }
 
+#[test]
+fn contains_raw() {
+    let set = FrozenSet::try_from_iter(std::iter::once(cell_index!(
+        0x8a1fb46622dffff
+    )))
+    .expect("failed to create set");
+
+    assert_eq!(
+        set.contains_raw(0x8d1fb46622d85bf),
+        Some(0x8a1fb46622dffff),
+        "descendant"
+    );
+    assert_eq!(set.contains_raw(0x85283473fffffff), None, "not related");
+    assert_eq!(set.contains_raw(0xdead_beef_dead_beef), None, "garbage");
+    assert_eq!(set.contains_raw(0), None, "zero");
+}
+
+#[test]
+fn present_subset() {
+    let cell = cell_index!(0x8a1fb46622dffff);
+    let other = cell_index!(0x85283473fffffff);
+    let set =
+        FrozenSet::try_from_iter([cell, other]).expect("failed to create set");
+
+    let result = set.present_subset([
+        cell_index!(0x8d1fb46622d85bf),
+        other,
+        cell_index!(0x85318d83fffffff),
+        cell_index!(0x8b1fb46622d8fff),
+        cell,
+    ]);
+    assert_eq!(result, vec![cell, other]);
+}
+
+#[test]
+fn contains_any() {
+    let cell = cell_index!(0x8a1fb46622dffff);
+    let set = FrozenSet::try_from_iter(std::iter::once(cell))
+        .expect("failed to create set");
+
+    let misses = [
+        cell_index!(0x85283473fffffff),
+        cell_index!(0x85318d83fffffff),
+    ];
+    assert!(!set.contains_any(misses), "misses");
+
+    let mut queried = Vec::new();
+    let candidates = misses
+        .into_iter()
+        .chain([
+            cell_index!(0x8d1fb46622d85bf),
+            cell_index!(0x8aa88b946a27fff),
+        ])
+        .inspect(|&cell| queried.push(cell));
+    assert!(set.contains_any(candidates), "hit");
+    assert_eq!(queried.len(), 3, "stop at the first hit");
+
+    assert!(!set.contains_any(std::iter::empty()), "no candidate");
+}
+
+#[test]
+fn present_neighbors() {
+    let center = cell_index!(0x8a1fb46622dffff);
+    let mut disk = center.grid_disk::<Vec<_>>(1);
+    disk.sort_unstable();
+    let neighbors = disk
+        .iter()
+        .copied()
+        .filter(|&cell| cell != center)
+        .collect::<Vec<_>>();
+    // Keep the center and half of its neighbors, plus an unrelated cell.
+    let present = neighbors.iter().copied().step_by(2).collect::<Vec<_>>();
+    let set = FrozenSet::try_from_iter(
+        disk.iter()
+            .copied()
+            .filter(|cell| *cell == center || present.contains(cell))
+            .chain(std::iter::once(cell_index!(0x85283473fffffff))),
+    )
+    .expect("failed to create set");
+    assert_eq!(set.present_neighbors(center), present, "fine cells");
+
+    // Neighbors covered by the same ancestor collapse into it.
+    let parent = center.parent(Resolution::Six).expect("parent");
+    let set = FrozenSet::try_from_iter(std::iter::once(parent))
+        .expect("failed to create set");
+    assert_eq!(set.present_neighbors(center), vec![parent], "ancestor");
+}
+
+#[test]
+fn k_nearest() {
+    let origin = cell_index!(0x8a1fb46622dffff);
+    let mut rings = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+    for (cell, distance) in origin.grid_disk_distances::<Vec<_>>(3) {
+        rings[distance as usize].push(cell);
+    }
+    for ring in &mut rings {
+        ring.sort_unstable();
+    }
+    // Two cells at distance 1, one at distance 2 and one at distance 3.
+    let mut cells = vec![
+        rings[1][1],
+        rings[1][4],
+        rings[2][3],
+        rings[3][0],
+        cell_index!(0x85283473fffffff),
+    ];
+    cells.sort_unstable();
+    let set = FrozenSet::try_from_iter(cells).expect("failed to create set");
+    let coord = LatLng::from(origin);
+
+    let result = set.k_nearest(coord, Resolution::Ten, 3, 5);
+    assert_eq!(
+        result,
+        vec![rings[1][1], rings[1][4], rings[2][3]],
+        "closest first"
+    );
+
+    let result = set.k_nearest(coord, Resolution::Ten, 10, 2);
+    assert_eq!(result.len(), 3, "limited by max_k_rings");
+
+    let result = set.k_nearest(coord, Resolution::Ten, 10, 5);
+    assert_eq!(result.len(), 4, "all reachable cells");
+    assert_eq!(result[3], rings[3][0], "farthest last");
+
+    assert!(
+        set.k_nearest(coord, Resolution::Ten, 0, 5).is_empty(),
+        "k = 0"
+    );
+}
+
+#[test]
+fn contains_base_cell() {
+    let base_cell = cell_index!(0x801ffffffffffff);
+    let descendant = cell_index!(0x8d1fb46622d85bf);
+    let set = FrozenSet::try_from_iter(std::iter::once(base_cell))
+        .expect("failed to create set");
+
+    assert_eq!(set.contains(base_cell), Some(base_cell), "exact match");
+    assert_eq!(set.contains(descendant), Some(base_cell), "descendant");
+    assert_eq!(
+        set.contains_depth(descendant),
+        Some((base_cell, Resolution::Zero)),
+        "depth"
+    );
+    let not_related = cell_index!(0x8029fffffffffff);
+    assert!(set.contains(not_related).is_none(), "other base cell");
+
+    // Querying a base cell with only descendants in the set.
+    let set = FrozenSet::try_from_iter(std::iter::once(descendant))
+        .expect("failed to create set");
+    assert!(set.contains(base_cell).is_none(), "ancestor query");
+}
+
+#[test]
+fn contains_depth() {
+    let set = FrozenSet::try_from_iter(vec![
+        cell_index!(0x8a1fb46622dffff),
+        cell_index!(0x85283473fffffff),
+    ])
+    .expect("failed to create set");
+
+    for cell in [
+        cell_index!(0x85283473fffffff),
+        cell_index!(0x8a2834701ab7fff),
+        cell_index!(0x8a1fb46622dffff),
+        cell_index!(0x8d1fb46622d85bf),
+    ] {
+        let (matched, resolution) =
+            set.contains_depth(cell).expect("contained cell");
+        assert_eq!(Some(matched), set.contains(cell), "same match");
+        assert_eq!(resolution, matched.resolution(), "match resolution");
+    }
+
+    let not_related = cell_index!(0x85318d83fffffff);
+    assert!(set.contains_depth(not_related).is_none(), "not related");
+}
+
+#[test]
+fn contains_traced() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let cell = parent
+        .children(Resolution::Eleven)
+        .nth(1000)
+        .expect("descendant");
+    let compacted = FrozenSet::try_from_iter(std::iter::once(parent))
+        .expect("failed to create set");
+    let expanded =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+
+    let (compacted_match, compacted_steps) = compacted.contains_traced(cell);
+    let (expanded_match, expanded_steps) = expanded.contains_traced(cell);
+    assert_eq!(compacted_match, Some(parent), "compacted match");
+    assert_eq!(
+        expanded_match,
+        cell.parent(Resolution::Seven),
+        "expanded match"
+    );
+    assert!(compacted_steps < expanded_steps, "shorter lookup path");
+
+    let missing = cell_index!(0x8a1fb46622dffff);
+    assert_eq!(expanded.contains_traced(missing), (None, 0), "miss");
+}
+
+#[test]
+fn contains_finest() {
+    let coarse = cell_index!(0x85318d83fffffff);
+    let fine = coarse
+        .children(Resolution::Eight)
+        .nth(100)
+        .expect("descendant");
+    let set =
+        FrozenSet::try_from_iter([coarse, fine]).expect("failed to create set");
+
+    let query = fine.center_child(Resolution::Ten).expect("center child");
+    assert_eq!(set.contains(query), Some(coarse), "coarsest");
+    assert_eq!(set.contains_finest(query), Some(fine), "finest");
+    assert_eq!(set.contains_finest(fine), Some(fine), "exact match");
+
+    let other = coarse.center_child(Resolution::Nine).expect("center child");
+    assert_eq!(set.contains_finest(other), Some(coarse), "single ancestor");
+
+    let not_related = cell_index!(0x85283473fffffff);
+    assert!(set.contains_finest(not_related).is_none(), "not related");
+}
+
+#[test]
+fn explain_contains() {
+    let cell = cell_index!(0x8a1fb46622dffff);
+    let set = FrozenSet::try_from_iter(std::iter::once(cell))
+        .expect("failed to create set");
+
+    // Near miss: the lookup diverges at the last resolution.
+    let sibling = cell
+        .parent(Resolution::Nine)
+        .expect("parent")
+        .children(Resolution::Ten)
+        .find(|&sibling| sibling != cell)
+        .expect("sibling");
+    let mut expected = Resolution::range(Resolution::Zero, Resolution::Nine)
+        .map(|resolution| (resolution, true, false))
+        .collect::<Vec<_>>();
+    expected.push((Resolution::Ten, false, false));
+    assert_eq!(set.explain_contains(sibling).steps, expected, "near miss");
+
+    // The walk goes on after a match.
+    let child = cell_index!(0x8b1fb46622d8fff);
+    let steps = set.explain_contains(child).steps;
+    assert_eq!(steps.len(), 12, "one step per resolution");
+    assert_eq!(steps[10], (Resolution::Ten, true, true), "match");
+    assert_eq!(steps[11], (Resolution::Eleven, false, false), "after match");
+}
+
 #[test]
 fn load_from_bytes() {
     // Build set in memory.
@@ -75,6 +453,137 @@ fn load_from_bytes() {
     );
 }
 
+#[test]
+fn shared() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let shared = set.shared();
+
+    let handles = [shared.clone(), shared.clone()].map(|set| {
+        std::thread::spawn(move || {
+            test_cells()
+                .filter(|&cell| set.contains(cell).is_some())
+                .count()
+        })
+    });
+    for handle in handles {
+        assert_eq!(handle.join().expect("thread panicked"), 49);
+    }
+
+    let reloaded = FrozenSet::from_arc(std::sync::Arc::from(set.as_bytes()))
+        .expect("failed to load set");
+    assert_eq!(
+        reloaded.iter().collect::<Vec<_>>(),
+        shared.iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn version_mismatch() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    // The format version is stored in the first 8 bytes.
+    let mut bytes = set.as_bytes().to_vec();
+    bytes[..8].copy_from_slice(&99_u64.to_le_bytes());
+
+    let result = FrozenSet::new(bytes);
+    assert!(
+        matches!(
+            result,
+            Err(BuildError::VersionMismatch {
+                expected: 3,
+                found: 99
+            })
+        ),
+        "version mismatch"
+    );
+}
+
+#[test]
+fn checksum() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let mut bytes = set.as_bytes().to_vec();
+
+    let result = FrozenSet::read_verified(bytes.as_slice()).expect("valid set");
+    assert_eq!(
+        result.iter().collect::<Vec<_>>(),
+        set.iter().collect::<Vec<_>>(),
+        "round-trip"
+    );
+
+    let idx = bytes.len() / 2;
+    bytes[idx] ^= 0xff;
+    let result = FrozenSet::read_verified(bytes.as_slice());
+    assert!(
+        matches!(result, Err(BuildError::ChecksumMismatch)),
+        "corrupted data"
+    );
+}
+
+#[test]
+fn is_valid() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    assert!(set.is_valid(), "valid set");
+    assert!(FrozenSet::default().is_valid(), "empty set");
+
+    // A well-formed FST whose keys aren't cell indexes.
+    for keys in [
+        vec![vec![122]],
+        vec![vec![0xff]],
+        vec![vec![21, 0], vec![21, 0, 9]],
+    ] {
+        let bytes = fst::Set::from_iter(keys)
+            .expect("failed to create FST")
+            .into_fst()
+            .into_inner();
+        let set = FrozenSet::new(bytes).expect("failed to load set");
+        assert!(!set.is_valid(), "invalid keys");
+    }
+}
+
+#[test]
+fn to_owned() {
+    let bytes = FrozenSet::try_from_iter(test_cells())
+        .expect("failed to create set")
+        .as_bytes()
+        .to_vec();
+
+    let owned = {
+        let borrowed = FrozenSet::new(bytes.as_slice()).expect("valid set");
+        borrowed.to_owned()
+    };
+    drop(bytes);
+
+    assert_eq!(
+        owned.iter().collect::<Vec<_>>(),
+        test_cells().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn append() {
+    let before = cell_index!(0x85283473fffffff);
+    let after = cell_index!(0x8aa88b946a27fff);
+    let set = FrozenSet::try_from_iter(
+        cell_index!(0x85318d83fffffff).children(Resolution::Six),
+    )
+    .expect("failed to create set");
+
+    let result = set
+        .append(vec![before, cell_index!(0x86318d817ffffff), after])
+        .expect("failed to append");
+    let expected = std::iter::once(before)
+        .chain(cell_index!(0x85318d83fffffff).children(Resolution::Six))
+        .chain(std::iter::once(after))
+        .collect::<Vec<_>>();
+    assert_eq!(result.iter().collect::<Vec<_>>(), expected, "appended");
+
+    let result = set.append(vec![after, before]);
+    assert!(result.is_err(), "appended out of order");
+}
+
 #[test]
 fn io_build() {
     let buffer = Cursor::new(Vec::new());
@@ -86,6 +595,196 @@ fn io_build() {
     builder.finish().expect("flushing set");
 }
 
+#[test]
+fn finish_compacted() {
+    let mut builder = FrozenSetBuilder::memory();
+    builder.extend_iter(test_cells()).expect("failed to extend");
+    let set = builder.finish_compacted().expect("failed to compact");
+
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        vec![cell_index!(0x85318d83fffffff)]
+    );
+}
+
+#[test]
+fn with_capacity() {
+    let mut builder = FrozenSetBuilder::with_capacity(1024);
+    builder.extend_iter(test_cells()).expect("failed to extend");
+    assert_eq!(builder.into_set().len(), 49, "infallible");
+
+    let mut builder =
+        FrozenSetBuilder::try_with_capacity(1024).expect("builder");
+    builder.extend_iter(test_cells()).expect("failed to extend");
+    assert_eq!(builder.into_set().len(), 49, "fallible");
+
+    let result = FrozenSetBuilder::try_with_capacity(usize::MAX);
+    assert!(
+        matches!(result, Err(BuildError::Alloc(_))),
+        "allocation failure"
+    );
+}
+
+#[test]
+fn try_from_mask() {
+    let set = FrozenSet::try_from_mask(
+        test_cells()
+            .enumerate()
+            .map(|(idx, cell)| (cell, idx % 3 == 0)),
+    )
+    .expect("failed to create set");
+    let expected = test_cells().step_by(3).collect::<Vec<_>>();
+    assert_eq!(set.iter().collect::<Vec<_>>(), expected, "flagged cells");
+
+    let result = FrozenSet::try_from_mask([
+        (cell_index!(0x85318d93fffffff), true),
+        (cell_index!(0x85318d83fffffff), true),
+    ]);
+    assert!(
+        matches!(result, Err(BuildError::OutOfOrder { .. })),
+        "out of order"
+    );
+
+    let result = FrozenSet::try_from_mask([
+        (cell_index!(0x85318d83fffffff), true),
+        (cell_index!(0x85283473fffffff), false),
+        (cell_index!(0x85318d93fffffff), true),
+    ]);
+    assert!(
+        matches!(
+            result,
+            Err(BuildError::OutOfOrder { previous, attempted })
+                if previous == cell_index!(0x85318d83fffffff)
+                    && attempted == cell_index!(0x85283473fffffff)
+        ),
+        "unflagged cell out of order"
+    );
+}
+
+#[test]
+fn try_from_grouped() {
+    let base15 = cell_index!(0x8a1fb46622dffff)
+        .parent(Resolution::Five)
+        .expect("parent")
+        .children(Resolution::Six)
+        .collect::<Vec<_>>();
+    let base24 = test_cells().collect::<Vec<_>>();
+    let expected = base15.iter().chain(&base24).copied().collect::<Vec<_>>();
+    let mut shuffled15 = base15;
+    shuffled15.reverse();
+    let mut shuffled24 = base24;
+    shuffled24.rotate_left(17);
+
+    let set = FrozenSet::try_from_grouped([
+        (15, shuffled15.clone()),
+        (24, shuffled24.clone()),
+    ])
+    .expect("failed to create set");
+    let result = set.iter().collect::<Vec<_>>();
+    assert_eq!(result, expected, "groups sorted");
+
+    let result = FrozenSet::try_from_grouped([
+        (24, shuffled24.clone()),
+        (15, shuffled15.clone()),
+    ]);
+    assert!(
+        matches!(result, Err(BuildError::InvalidGroup { base_cell: 15 })),
+        "base cells out of order"
+    );
+
+    let result =
+        FrozenSet::try_from_grouped([(15, shuffled24), (24, shuffled15)]);
+    assert!(
+        matches!(result, Err(BuildError::InvalidGroup { base_cell: 15 })),
+        "mislabelled group"
+    );
+}
+
+#[test]
+fn try_from_ranges() {
+    let cells = test_cells().collect::<Vec<_>>();
+    let set = FrozenSet::try_from_ranges([
+        (cells[0], cells[20]),
+        (cells[21], cells[48]),
+    ])
+    .expect("failed to create set");
+    let expected =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        expected.iter().collect::<Vec<_>>(),
+        "adjacent ranges"
+    );
+
+    let result = FrozenSet::try_from_ranges([(
+        cell_index!(0x85318d83fffffff),
+        cells[48],
+    )]);
+    assert!(
+        matches!(result, Err(BuildError::MixedResolutions)),
+        "mixed resolutions"
+    );
+
+    let result = FrozenSet::try_from_ranges([
+        (cells[0], cells[20]),
+        (cells[10], cells[30]),
+    ]);
+    assert!(result.is_err(), "overlapping ranges");
+}
+
+#[test]
+fn try_from_reader_lines() {
+    let input = "8a1fb46622dffff\n  85318d83fffffff \n\n8a1fb46622dffff\n";
+    let set = FrozenSet::try_from_reader_lines(input.as_bytes())
+        .expect("failed to create set");
+    let result = set.iter().collect::<Vec<_>>();
+    assert_eq!(
+        result,
+        vec![
+            cell_index!(0x8a1fb46622dffff),
+            cell_index!(0x85318d83fffffff)
+        ],
+        "sorted and deduplicated"
+    );
+
+    let input = "8a1fb46622dffff\n85318d83fffffff\nnot a cell\n";
+    let result = FrozenSet::try_from_reader_lines(input.as_bytes());
+    assert!(
+        matches!(result, Err(BuildError::InvalidCell { line: 3, .. })),
+        "malformed line"
+    );
+
+    let input = b"8a1fb46622dffff\n\xff\n";
+    let result = FrozenSet::try_from_reader_lines(&input[..]);
+    assert!(matches!(result, Err(BuildError::Io(_))), "read error");
+}
+
+#[test]
+fn reset() {
+    let mut builder = FrozenSetBuilder::memory();
+    builder.extend_iter(test_cells()).expect("failed to extend");
+    builder.reset();
+
+    // Would be out of order without the reset.
+    let cells = [
+        cell_index!(0x8a1fb46622dffff),
+        cell_index!(0x85283473fffffff),
+    ];
+    builder.extend_iter(cells).expect("failed to extend");
+    let set = builder.into_set();
+
+    assert_eq!(set.iter().collect::<Vec<_>>(), cells);
+
+    // The buffer is reused.
+    let capacity = 1 << 20;
+    let mut builder = FrozenSetBuilder::try_with_capacity(capacity)
+        .expect("failed to allocate");
+    builder.extend_iter(test_cells()).expect("failed to extend");
+    builder.reset();
+    let buffer = builder.into_inner().expect("failed to finish");
+    assert!(buffer.capacity() >= capacity, "capacity kept");
+}
+
 #[test]
 fn wrong_order() {
     // Building set from non-sorted input fails.
@@ -97,21 +796,180 @@ fn wrong_order() {
         .insert(cell_index!(0x85283473fffffff))
         .expect_err("inserted out of order");
 
-    assert!(err.source().is_some(), "preserve root cause");
+    assert!(
+        matches!(
+            err,
+            BuildError::OutOfOrder { previous, attempted }
+                if previous == cell_index!(0x85318d83fffffff)
+                    && attempted == cell_index!(0x85283473fffffff)
+        ),
+        "report offending cells"
+    );
     assert!(!err.to_string().is_empty(), "non-empty error");
 }
 
 #[test]
-fn range() {
-    let set = FrozenSet::try_from_iter(
-        cell_index!(0x85318d83fffffff).children(Resolution::Six),
-    )
-    .expect("failed to create set");
+fn insert_dedup() {
+    let a = cell_index!(0x85283473fffffff);
+    let b = cell_index!(0x85318d83fffffff);
 
-    // A (half-open) range bounded inclusively below and exclusively above.
-    let result = set
-        .range((
-            Bound::Included(cell_index!(0x86318d817ffffff)),
+    let mut builder = FrozenSetBuilder::memory();
+    builder.insert_dedup(a).expect("failed to insert");
+    builder.insert_dedup(a).expect("duplicate is ignored");
+    builder.insert_dedup(b).expect("failed to insert");
+    let set = builder.into_set();
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![a, b], "deduplicated");
+
+    let mut builder = FrozenSetBuilder::memory();
+    builder.insert_dedup(b).expect("failed to insert");
+    let err = builder.insert_dedup(a).expect_err("inserted out of order");
+    assert!(
+        matches!(err, BuildError::OutOfOrder { previous, attempted }
+            if previous == b && attempted == a),
+        "out of order"
+    );
+}
+
+#[test]
+fn extend_checked() {
+    let mut cells = test_cells().take(6).collect::<Vec<_>>();
+    cells.swap(2, 3);
+
+    let mut builder = FrozenSetBuilder::memory();
+    let result = builder.extend_checked(cells);
+    assert!(
+        matches!(result, Err((3, BuildError::OutOfOrder { .. }))),
+        "position of the out of order cell"
+    );
+    let set = builder.into_set();
+    let expected = test_cells().take(2).chain(test_cells().nth(3));
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        expected.collect::<Vec<_>>(),
+        "inserted cells"
+    );
+
+    let mut builder = FrozenSetBuilder::memory();
+    let count = builder
+        .extend_checked(test_cells())
+        .expect("failed to insert");
+    assert_eq!(count, test_cells().count(), "ordered input");
+}
+
+#[test]
+fn strict_resolution() {
+    let mut builder =
+        FrozenSetBuilder::memory().strict_resolution(Resolution::Seven);
+    builder
+        .extend_iter(test_cells().take(3))
+        .expect("failed to insert");
+    let err = builder
+        .insert(cell_index!(0x85318d93fffffff))
+        .expect_err("inserted at another resolution");
+
+    assert!(
+        matches!(
+            err,
+            BuildError::ResolutionMismatch { expected, attempted }
+                if expected == Resolution::Seven
+                    && attempted == cell_index!(0x85318d93fffffff)
+        ),
+        "report offending cell"
+    );
+    assert!(!err.to_string().is_empty(), "non-empty error");
+
+    let set = builder.into_set();
+    let expected = test_cells().take(3).collect::<Vec<_>>();
+    assert_eq!(set.iter().collect::<Vec<_>>(), expected, "cell rejected");
+}
+
+#[test]
+fn iter_u64() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let result = set.iter_u64().collect::<Vec<_>>();
+    let expected = set.iter().map(u64::from).collect::<Vec<_>>();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn iter_debug() {
+    let index = cell_index!(0x85318d83fffffff);
+    let set = FrozenSet::try_from_iter(std::iter::once(index))
+        .expect("failed to create set");
+    let result = set.iter_debug().collect::<Vec<_>>();
+
+    // Base cell, then one direction per resolution.
+    let mut key = vec![u8::from(index.base_cell())];
+    key.extend(Resolution::range(Resolution::One, index.resolution()).map(
+        |res| u8::from(index.direction_at(res).expect("resolution in range")),
+    ));
+    assert_eq!(result, vec![(index, key)], "known cell");
+
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let cells = set.iter_debug().map(|(cell, _)| cell).collect::<Vec<_>>();
+    assert_eq!(cells, set.to_vec(), "same order as iter");
+}
+
+#[test]
+fn packed_cells() {
+    // Mixed resolutions.
+    let cell = cell_index!(0x86318d937ffffff);
+    let set = FrozenSet::try_from_iter(vec![
+        cell_index!(0x85318d83fffffff),
+        cell,
+        cell.center_child(Resolution::Ten).expect("center child"),
+    ])
+    .expect("failed to create set");
+    let result = FrozenSet::from_packed_cells(&set.to_packed_cells())
+        .expect("failed to unpack");
+    assert!(result == set, "round-trip");
+
+    // Fine cells scattered around the globe.
+    let mut cells = (0..100)
+        .map(|i| {
+            let i = f64::from(i);
+            LatLng::new(-80. + i * 1.61, -180. + i * 3.57)
+                .expect("valid coordinate")
+                .to_cell(Resolution::Fifteen)
+        })
+        .collect::<Vec<_>>();
+    cells.sort_unstable();
+    let set = FrozenSet::try_from_iter(cells).expect("failed to create set");
+    let bytes = set.to_packed_cells();
+    assert!(
+        bytes.len() < set.as_bytes().len(),
+        "smaller for sparse sets"
+    );
+    let result =
+        FrozenSet::from_packed_cells(&bytes).expect("failed to unpack");
+    assert!(result == set, "sparse round-trip");
+
+    let result = FrozenSet::from_packed_cells(&[0x80]);
+    assert!(
+        matches!(result, Err(BuildError::InvalidPackedCells)),
+        "truncated"
+    );
+    let result = FrozenSet::from_packed_cells(&[42]);
+    assert!(
+        matches!(result, Err(BuildError::InvalidPackedCells)),
+        "invalid cell"
+    );
+}
+
+#[test]
+fn range() {
+    let set = FrozenSet::try_from_iter(
+        cell_index!(0x85318d83fffffff).children(Resolution::Six),
+    )
+    .expect("failed to create set");
+
+    // A (half-open) range bounded inclusively below and exclusively above.
+    let result = set
+        .range((
+            Bound::Included(cell_index!(0x86318d817ffffff)),
             Bound::Excluded(cell_index!(0x86318d827ffffff)),
         ))
         .collect::<Vec<_>>();
@@ -208,6 +1066,134 @@ fn range() {
     assert_eq!(result, expected, "RangeToInclusive");
 }
 
+#[test]
+fn range_bbox() {
+    let set = FrozenSet::try_from_iter(
+        cell_index!(0x85318d83fffffff).children(Resolution::Six),
+    )
+    .expect("failed to create set");
+    let sw = LatLng::from(cell_index!(0x86318d827ffffff));
+    let ne = LatLng::from(cell_index!(0x86318d817ffffff));
+
+    let result = set.range_bbox(sw, ne, Resolution::Six).collect::<Vec<_>>();
+    let expected = vec![
+        cell_index!(0x86318d817ffffff),
+        cell_index!(0x86318d81fffffff),
+        cell_index!(0x86318d827ffffff),
+    ];
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn iter_base_cell() {
+    let first = cell_index!(0x85283473fffffff)
+        .children(Resolution::Six)
+        .collect::<Vec<_>>();
+    let second = cell_index!(0x85318d83fffffff)
+        .children(Resolution::Six)
+        .collect::<Vec<_>>();
+    let set = FrozenSet::try_from_iter(first.iter().chain(&second).copied())
+        .expect("failed to create set");
+
+    let result = set.iter_base_cell(20).collect::<Vec<_>>();
+    assert_eq!(result, first, "first base cell");
+
+    let result = set.iter_base_cell(24).collect::<Vec<_>>();
+    assert_eq!(result, second, "second base cell");
+
+    let result = set.iter_base_cell(0).count();
+    assert_eq!(result, 0, "missing base cell");
+}
+
+#[test]
+fn leaves() {
+    let other = cell_index!(0x85283473fffffff);
+    let parent = cell_index!(0x85318d83fffffff);
+    let children = parent.children(Resolution::Six).take(2).collect::<Vec<_>>();
+    let grandchildren = children[0]
+        .children(Resolution::Seven)
+        .take(2)
+        .collect::<Vec<_>>();
+    let set = FrozenSet::try_from_iter(
+        [other, parent, children[0]]
+            .into_iter()
+            .chain(grandchildren.iter().copied())
+            .chain(std::iter::once(children[1])),
+    )
+    .expect("failed to create set");
+
+    let result = set.leaves().collect::<Vec<_>>();
+    let expected = vec![other, grandchildren[0], grandchildren[1], children[1]];
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn iter_from() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+
+    // Page through the set, 10 by 10.
+    let mut result = Vec::new();
+    let mut last = None;
+    loop {
+        let page = match last {
+            Some(cell) => set.iter_from(cell).skip(1).take(10).collect(),
+            None => set.iter().take(10).collect::<Vec<_>>(),
+        };
+        if page.is_empty() {
+            break;
+        }
+        last = page.last().copied();
+        result.extend(page);
+    }
+    let expected = test_cells().collect::<Vec<_>>();
+    assert_eq!(result, expected, "no gap nor duplicate");
+
+    let beyond = cell_index!(0x85318d93fffffff);
+    assert_eq!(set.iter_from(beyond).count(), 0, "beyond the last key");
+}
+
+#[test]
+fn page() {
+    let cells = test_cells().take(10).collect::<Vec<_>>();
+    let set =
+        FrozenSet::try_from_iter(cells.clone()).expect("failed to create set");
+
+    let limit = NonZeroUsize::new(4).expect("non-zero limit");
+    let (first, token) = set.page(None, limit);
+    assert_eq!(first, cells[..4], "first page");
+    assert_eq!(token, Some(cells[3]), "first token");
+
+    let (second, token) = set.page(token, limit);
+    assert_eq!(second, cells[4..8], "second page");
+    assert_eq!(token, Some(cells[7]), "second token");
+
+    let (last, token) = set.page(token, limit);
+    assert_eq!(last, cells[8..], "last page");
+    assert_eq!(token, None, "exhausted");
+
+    let limit = NonZeroUsize::new(10).expect("non-zero limit");
+    let (result, token) = set.page(None, limit);
+    assert_eq!(result, cells, "exact fit");
+    assert_eq!(token, None, "exact fit token");
+}
+
+#[test]
+fn range_fused() {
+    let set = FrozenSet::try_from_iter(
+        cell_index!(0x85318d83fffffff).children(Resolution::Six),
+    )
+    .expect("failed to create set");
+
+    let mut iter = set.range((
+        Bound::Included(cell_index!(0x86318d817ffffff)),
+        Bound::Excluded(cell_index!(0x86318d81fffffff)),
+    ));
+    assert!(iter.next().is_some(), "first item");
+    assert!(iter.next().is_none(), "exhausted");
+    assert!(iter.next().is_none(), "still exhausted");
+}
+
 #[test]
 fn descendants() {
     let set = FrozenSet::try_from_iter(
@@ -233,6 +1219,810 @@ fn descendants() {
     assert_eq!(result, 14, "grand-parent");
 }
 
+#[test]
+fn descendants_base_cell() {
+    let set = FrozenSet::try_from_iter(
+        [cell_index!(0x801ffffffffffff)]
+            .into_iter()
+            .chain(test_cells())
+            .chain(std::iter::once(cell_index!(0x8033fffffffffff))),
+    )
+    .expect("failed to create set");
+
+    let result = set.descendants(cell_index!(0x8031fffffffffff)).count();
+    assert_eq!(result, 49, "base cell");
+
+    let result = set.descendants(cell_index!(0x801ffffffffffff)).count();
+    assert_eq!(result, 0, "stored base cell");
+}
+
+#[test]
+fn descendants_res14() {
+    let parent = cell_index!(0x8e2a1072b598647);
+    let set = FrozenSet::try_from_iter(parent.children(Resolution::Fifteen))
+        .expect("failed to create set");
+
+    let result = set.descendants(parent).collect::<Vec<_>>();
+    let expected = parent.children(Resolution::Fifteen).collect::<Vec<_>>();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn descendants_of_many() {
+    let a = cell_index!(0x85318d83fffffff);
+    let b = cell_index!(0x85318d93fffffff);
+    let set = FrozenSet::try_from_iter(
+        test_cells().chain(b.children(Resolution::Six)),
+    )
+    .expect("failed to create set");
+    let all = set.iter().collect::<Vec<_>>();
+
+    let result = set.descendants_of_many([b, a]).collect::<Vec<_>>();
+    assert_eq!(result, all, "disjoint parents");
+
+    let child = a.center_child(Resolution::Six).expect("center child");
+    let result = set.descendants_of_many([child, a]).collect::<Vec<_>>();
+    let expected = set.descendants(a).collect::<Vec<_>>();
+    assert_eq!(result, expected, "nested parents");
+
+    let grandparent = a.parent(Resolution::Four).expect("parent");
+    let result = set
+        .descendants_of_many([a, grandparent, child])
+        .collect::<Vec<_>>();
+    assert_eq!(result, all, "common ancestor");
+
+    let result = set.descendants_of_many(std::iter::empty()).count();
+    assert_eq!(result, 0, "no parent");
+}
+
+#[test]
+fn try_descendants() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let result = set
+        .try_descendants(parent)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("valid keys");
+    // Same range as `descendants`.
+    let expected = set.descendants(parent).collect::<Vec<_>>();
+    assert_eq!(result, expected, "valid data");
+
+    // A well-formed FST whose last key isn't a cell index.
+    let bytes = fst::Set::from_iter([vec![21], vec![21, 0], vec![21, 0, 9]])
+        .expect("failed to create FST")
+        .into_fst()
+        .into_inner();
+    let set = FrozenSet::new(bytes).expect("failed to load set");
+    let base_cell = cell_index!(0x802bfffffffffff);
+    let result = set.try_descendants(base_cell).collect::<Vec<_>>();
+    assert_eq!(result.len(), 2, "every key");
+    assert!(
+        matches!(result[0], Ok(cell)
+            if Some(cell) == base_cell.center_child(Resolution::One)),
+        "valid key"
+    );
+    assert!(
+        matches!(result[1], Err(BuildError::InvalidKey)),
+        "invalid key"
+    );
+}
+
+#[test]
+fn descendants_within() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let center = parent.center_child(Resolution::Seven).expect("center");
+    let set = FrozenSet::try_from_iter(
+        std::iter::once(parent.center_child(Resolution::Six).expect("center"))
+            .chain(test_cells()),
+    )
+    .expect("failed to create set");
+
+    let result = set
+        .descendants_within(parent, Resolution::Seven, 1)
+        .collect::<Vec<_>>();
+    let mut expected = center.grid_disk::<Vec<_>>(1);
+    expected.sort_unstable();
+    assert_eq!(result, expected, "first ring");
+
+    let result = set
+        .descendants_within(parent, Resolution::Seven, 0)
+        .collect::<Vec<_>>();
+    assert_eq!(result, vec![center], "center only");
+
+    let result = set
+        .descendants_within(parent, Resolution::Seven, 10)
+        .count();
+    assert_eq!(result, 49, "whole subtree");
+}
+
+#[test]
+fn for_each_descendant() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+
+    let mut visited = Vec::new();
+    set.for_each_descendant(parent, |cell| {
+        visited.push(cell);
+        if visited.len() == 3 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    let expected = test_cells().take(3).collect::<Vec<_>>();
+    assert_eq!(visited, expected, "stopped after the third");
+
+    let mut count = 0;
+    set.for_each_descendant(parent, |_| {
+        count += 1;
+        ControlFlow::Continue(())
+    });
+    assert_eq!(count, 49, "every descendant");
+}
+
+#[test]
+fn subtree() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let set = FrozenSet::try_from_iter(
+        std::iter::once(parent)
+            .chain(test_cells())
+            .chain(std::iter::once(cell_index!(0x85318d93fffffff))),
+    )
+    .expect("failed to create set");
+
+    let result = set.subtree(parent).collect::<Vec<_>>();
+    let expected = std::iter::once(parent)
+        .chain(test_cells())
+        .collect::<Vec<_>>();
+    assert_eq!(result, expected, "parent leads");
+    assert_eq!(set.descendants(parent).count(), 49, "descendants only");
+
+    let leaf = cell_index!(0x87318d800ffffff);
+    assert_eq!(set.subtree(leaf).collect::<Vec<_>>(), vec![leaf], "leaf");
+
+    let missing = cell_index!(0x85318d8bfffffff);
+    assert_eq!(set.subtree(missing).count(), 0, "missing");
+}
+
+#[test]
+fn densest_child() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let dense = cell_index!(0x86318d807ffffff);
+    let sparse = cell_index!(0x86318d837ffffff);
+    let set = FrozenSet::try_from_iter(
+        dense
+            .children(Resolution::Eight)
+            .chain(sparse.children(Resolution::Seven)),
+    )
+    .expect("failed to create set");
+
+    assert_eq!(set.densest_child(parent), Some((dense, 49)), "densest");
+    assert_eq!(
+        set.densest_child(cell_index!(0x85318d93fffffff)),
+        None,
+        "no descendants"
+    );
+}
+
+#[test]
+fn has_at_least_descendants() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let set = FrozenSet::try_from_iter(parent.children(Resolution::Six))
+        .expect("failed to create set");
+
+    assert!(set.has_at_least_descendants(parent, 0), "zero");
+    assert!(set.has_at_least_descendants(parent, 6), "n-1 descendants");
+    assert!(
+        set.has_at_least_descendants(parent, 7),
+        "exactly n descendants"
+    );
+    assert!(!set.has_at_least_descendants(parent, 8), "n+1 descendants");
+
+    let leaf = cell_index!(0x86318d837ffffff);
+    assert!(!set.has_at_least_descendants(leaf, 1), "no descendants");
+}
+
+#[test]
+fn boundary_cells() {
+    let center = cell_index!(0x8a1fb46622dffff);
+    let mut disk = center.grid_disk::<Vec<_>>(1);
+    disk.sort_unstable();
+
+    // Every cell of a small block is on the boundary.
+    let block = disk.iter().copied().take(4).collect::<Vec<_>>();
+    let set =
+        FrozenSet::try_from_iter(block.clone()).expect("failed to create set");
+    let result = set.boundary_cells().collect::<Vec<_>>();
+    assert_eq!(result, block, "small block");
+
+    // The center of a disk isn't on the boundary.
+    let set =
+        FrozenSet::try_from_iter(disk.clone()).expect("failed to create set");
+    let result = set.boundary_cells().collect::<Vec<_>>();
+    let expected = disk
+        .into_iter()
+        .filter(|cell| *cell != center)
+        .collect::<Vec<_>>();
+    assert_eq!(result, expected, "disk");
+}
+
+#[test]
+fn attach_values() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+
+    let map = set.attach_values(100..).expect("failed to attach values");
+    let result = map.iter().collect::<Vec<_>>();
+    let expected = test_cells().zip(100..).collect::<Vec<_>>();
+    assert_eq!(result, expected, "values");
+
+    let result = set.attach_values(0..10);
+    assert!(
+        matches!(result, Err(BuildError::MissingValues)),
+        "not enough values"
+    );
+}
+
+#[test]
+fn to_map_const() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+
+    let map = set.to_map_const(7).expect("failed to create map");
+    assert_eq!(map.len(), set.len(), "length");
+    for cell in test_cells().step_by(10) {
+        assert_eq!(map.get(cell), Some((cell, 7)), "{cell}");
+    }
+}
+
+#[test]
+fn bounding_cell() {
+    let set = FrozenSet::try_from_iter([
+        cell_index!(0x87318d800ffffff),
+        cell_index!(0x86318d837ffffff),
+    ])
+    .expect("failed to create set");
+    assert_eq!(
+        set.bounding_cell(),
+        Some(cell_index!(0x85318d83fffffff)),
+        "same parent"
+    );
+
+    // Every ancestor of the siblings covers them, only the finest is returned.
+    let a = cell_index!(0x8a1fb46622d7fff);
+    let b = cell_index!(0x8a1fb46622dffff);
+    let parent = a.parent(Resolution::Nine).expect("parent");
+    assert_eq!(b.parent(Resolution::Nine), Some(parent), "siblings");
+    let set = FrozenSet::try_from_iter([a, b]).expect("failed to create set");
+    assert_eq!(set.bounding_cell(), Some(parent), "finest common ancestor");
+
+    let set = FrozenSet::try_from_iter([cell_index!(0x85318d83fffffff)])
+        .expect("failed to create set");
+    assert_eq!(
+        set.bounding_cell(),
+        Some(cell_index!(0x85318d83fffffff)),
+        "single cell"
+    );
+
+    let set = FrozenSet::try_from_iter([
+        cell_index!(0x801ffffffffffff),
+        cell_index!(0x8031fffffffffff),
+    ])
+    .expect("failed to create set");
+    assert_eq!(set.bounding_cell(), None, "several base cells");
+
+    let set = FrozenSet::try_from_iter(std::iter::empty())
+        .expect("failed to create set");
+    assert_eq!(set.bounding_cell(), None, "empty");
+}
+
+#[test]
+fn overlap_count() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let parent = cell_index!(0x85318d83fffffff);
+
+    let mask = FrozenSet::try_from_iter(
+        parent.children(Resolution::Six).skip(1).step_by(2),
+    )
+    .expect("failed to create mask");
+    assert_eq!(set.overlap_count(&mask), 21, "partial mask");
+
+    let mask = FrozenSet::try_from_iter(std::iter::once(parent))
+        .expect("failed to create mask");
+    assert_eq!(set.overlap_count(&mask), set.len(), "full mask");
+
+    let mask = FrozenSet::try_from_iter([cell_index!(0x85318d93fffffff)])
+        .expect("failed to create mask");
+    assert_eq!(set.overlap_count(&mask), 0, "disjoint mask");
+}
+
+#[test]
+fn contiguous_runs() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let dense = FrozenSet::try_from_iter(parent.children(Resolution::Ten))
+        .expect("failed to create set");
+    let result = dense.contiguous_runs().collect::<Vec<_>>();
+    let first = parent.center_child(Resolution::Ten).expect("center child");
+    assert_eq!(result, vec![(first, 16_807)], "dense");
+
+    let sparse = FrozenSet::try_from_iter(test_cells().step_by(2))
+        .expect("failed to create set");
+    let result = sparse.contiguous_runs().collect::<Vec<_>>();
+    let expected = test_cells()
+        .step_by(2)
+        .map(|cell| (cell, 1))
+        .collect::<Vec<_>>();
+    assert_eq!(result, expected, "sparse");
+
+    // Different resolutions never form a run.
+    let mixed = FrozenSet::try_from_iter([
+        cell_index!(0x86318d807ffffff),
+        cell_index!(0x87318d808ffffff),
+    ])
+    .expect("failed to create set");
+    assert_eq!(mixed.contiguous_runs().count(), 2, "mixed resolutions");
+}
+
+#[test]
+fn iter_boundaries() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let (cell, boundary) = set.iter_boundaries().next().expect("first cell");
+
+    assert_eq!(cell, cell_index!(0x87318d800ffffff), "cell");
+    assert_eq!(boundary.len(), 6, "hexagon");
+    assert_eq!(set.iter_boundaries().count(), 49, "count");
+}
+
+#[test]
+fn to_wkt() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let wkt = set.to_wkt();
+
+    let polygons = wkt
+        .strip_prefix("MULTIPOLYGON (((")
+        .and_then(|wkt| wkt.strip_suffix(")))"))
+        .expect("multipolygon")
+        .split(")), ((")
+        .map(|ring| {
+            ring.split(", ")
+                .map(|vertex| {
+                    let (lng, lat) = vertex.split_once(' ').expect("vertex");
+                    (
+                        lng.parse::<f64>().expect("longitude"),
+                        lat.parse::<f64>().expect("latitude"),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(polygons.len(), 49, "one polygon per cell");
+
+    let first = cell_index!(0x87318d800ffffff).boundary();
+    assert_eq!(polygons[0].len(), first.len() + 1, "closed ring");
+    assert_eq!(polygons[0][0], polygons[0][first.len()], "same ends");
+    assert_eq!(
+        polygons[0][0],
+        (first[0].lng(), first[0].lat()),
+        "first vertex"
+    );
+
+    let mut buffer = Vec::new();
+    set.write_wkt(&mut buffer).expect("failed to write");
+    assert_eq!(buffer, wkt.as_bytes(), "same output when streamed");
+
+    let empty = FrozenSet::default();
+    assert_eq!(empty.to_wkt(), "MULTIPOLYGON EMPTY", "empty");
+}
+
+#[test]
+#[cfg(feature = "geo")]
+fn clip_to_polygon() {
+    use geo::{polygon, Contains, Point};
+
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let center = LatLng::from(cell_index!(0x85318d83fffffff));
+    let (lat, lng) = (center.lat(), center.lng());
+    // Small triangle in the north-east quarter of the parent cell.
+    let triangle = polygon![
+        (x: lng, y: lat),
+        (x: lng + 0.1, y: lat),
+        (x: lng + 0.05, y: lat + 0.1),
+    ];
+
+    let clipped = set
+        .clip_to_polygon(&triangle, Resolution::Seven)
+        .expect("failed to clip");
+    assert!(!clipped.is_empty(), "some cells inside");
+    assert!(clipped.len() < set.len(), "some cells outside");
+    for cell in &set {
+        let ll = LatLng::from(cell);
+        let inside = triangle.contains(&Point::new(ll.lng(), ll.lat()));
+        assert_eq!(clipped.contains(cell).is_some(), inside, "{cell}");
+    }
+
+    // Coarser cells are replaced by the covered cells.
+    let parent = FrozenSet::try_from_iter(std::iter::once(cell_index!(
+        0x85318d83fffffff
+    )))
+    .expect("failed to create set");
+    let result = parent
+        .clip_to_polygon(&triangle, Resolution::Seven)
+        .expect("failed to clip")
+        .iter()
+        .collect::<Vec<_>>();
+    assert_eq!(result, clipped.iter().collect::<Vec<_>>(), "coarse cell");
+}
+
+#[test]
+#[cfg(feature = "geojson")]
+fn try_from_geojson() {
+    use geojson::{Feature, FeatureCollection, Geometry, Value};
+
+    let center = LatLng::from(cell_index!(0x85318d83fffffff));
+    let (lat, lng) = (center.lat(), center.lng());
+    let triangle = vec![vec![
+        vec![lng, lat],
+        vec![lng + 0.1, lat],
+        vec![lng + 0.05, lat + 0.1],
+        vec![lng, lat],
+    ]];
+    let fc = FeatureCollection {
+        bbox: None,
+        features: vec![
+            Feature::from(Geometry::new(Value::Polygon(triangle.clone()))),
+            // Points don't cover an area and are skipped.
+            Feature::from(Geometry::new(Value::Point(vec![lng, lat]))),
+        ],
+        foreign_members: None,
+    };
+
+    let set = FrozenSet::try_from_geojson(&fc, Resolution::Seven)
+        .expect("failed to create set");
+    let polygon = geo::Polygon::try_from(Value::Polygon(triangle))
+        .expect("valid polygon");
+    let mut tiler = h3o::geom::TilerBuilder::new(Resolution::Seven).build();
+    tiler.add(polygon).expect("valid polygon");
+    let mut expected = tiler.into_coverage().collect::<Vec<_>>();
+    expected.sort_unstable();
+    expected.dedup();
+
+    assert!(!set.is_empty());
+    assert_eq!(set.iter().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn complement_within() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let (present, missing): (Vec<_>, Vec<_>) =
+        test_cells().enumerate().partition(|&(idx, _)| idx % 2 == 0);
+    let set =
+        FrozenSet::try_from_iter(present.into_iter().map(|(_, cell)| cell))
+            .expect("failed to create set");
+
+    let result = set
+        .complement_within(parent, Resolution::Seven)
+        .expect("complement")
+        .iter()
+        .collect::<Vec<_>>();
+    let expected = missing
+        .into_iter()
+        .map(|(_, cell)| cell)
+        .collect::<Vec<_>>();
+
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn missing_children() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let children = parent.children(Resolution::Six).collect::<Vec<_>>();
+    let set = FrozenSet::try_from_iter(
+        children
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, &cell)| (idx != 2 && idx != 5).then_some(cell)),
+    )
+    .expect("failed to create set");
+
+    let result = set.missing_children(parent, Resolution::Six);
+    assert_eq!(result, vec![children[2], children[5]], "two gaps");
+
+    let result = set.missing_children(children[0], Resolution::Seven);
+    assert!(result.is_empty(), "covered by an ancestor");
+}
+
+#[test]
+fn coverage_fraction() {
+    let parent = cell_index!(0x85318d83fffffff);
+    // A hexagon has 49 grandchildren, keep every other one.
+    let set = FrozenSet::try_from_iter(test_cells().step_by(2))
+        .expect("failed to create set");
+
+    let result = set.coverage_fraction(parent, Resolution::Seven);
+    assert!((result - 25. / 49.).abs() < f64::EPSILON, "about half");
+
+    let result = set.coverage_fraction(parent, Resolution::Five);
+    assert!(result == 0., "coarser resolution");
+
+    let set = FrozenSet::try_from_iter(std::iter::once(parent))
+        .expect("failed to create set");
+    let child = parent.children(Resolution::Six).next().expect("child");
+    let result = set.coverage_fraction(child, Resolution::Eight);
+    assert!(result == 1., "covered by an ancestor");
+
+    let other = cell_index!(0x85283473fffffff);
+    let result = set.coverage_fraction(other, Resolution::Seven);
+    assert!(result == 0., "not covered");
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn sample() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+
+    let result = set.sample(5, 42);
+    assert_eq!(result.len(), 5, "sample size");
+    assert_eq!(result, set.sample(5, 42), "reproducible");
+    assert!(
+        result.iter().all(|cell| set.contains(*cell).is_some()),
+        "sampled from the set"
+    );
+
+    let result = set.sample(100, 42);
+    assert_eq!(result, set.iter().collect::<Vec<_>>(), "whole set");
+}
+
+#[test]
+fn rescale() {
+    let coarse = cell_index!(0x85283473fffffff);
+    let parent = cell_index!(0x85318d83fffffff);
+    let fine = parent
+        .children(Resolution::Ten)
+        .take(500)
+        .collect::<Vec<_>>();
+    let set = FrozenSet::try_from_iter(
+        std::iter::once(coarse).chain(fine.iter().copied()),
+    )
+    .expect("failed to create set");
+
+    let result = set
+        .rescale(Resolution::Eight)
+        .expect("failed to rescale")
+        .iter()
+        .collect::<Vec<_>>();
+    assert!(
+        result
+            .iter()
+            .all(|cell| cell.resolution() == Resolution::Eight),
+        "single resolution"
+    );
+    let mut expected = coarse
+        .children(Resolution::Eight)
+        .chain(
+            fine.iter()
+                .filter_map(|cell| cell.parent(Resolution::Eight)),
+        )
+        .collect::<Vec<_>>();
+    expected.sort_unstable();
+    expected.dedup();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn resolution_range() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    assert_eq!(
+        set.resolution_range(),
+        Some((Resolution::Seven, Resolution::Seven)),
+        "single resolution"
+    );
+
+    let set = FrozenSet::try_from_iter(vec![
+        cell_index!(0x8a1fb46622dffff),
+        cell_index!(0x85283473fffffff),
+        cell_index!(0x8031fffffffffff),
+    ])
+    .expect("failed to create set");
+    assert_eq!(
+        set.resolution_range(),
+        Some((Resolution::Zero, Resolution::Ten)),
+        "mixed resolutions"
+    );
+
+    assert_eq!(FrozenSet::default().resolution_range(), None, "empty");
+}
+
+#[test]
+fn counts_by_resolution() {
+    let set = FrozenSet::try_from_iter(
+        std::iter::once(cell_index!(0x85318d83fffffff)).chain(
+            cell_index!(0x85318d93fffffff)
+                .children(Resolution::Ten)
+                .take(5),
+        ),
+    )
+    .expect("failed to create set");
+    let result = set.counts_by_resolution().collect::<Vec<_>>();
+    assert_eq!(
+        result,
+        vec![(Resolution::Five, 1), (Resolution::Ten, 5)],
+        "mixed resolutions"
+    );
+
+    let result = FrozenSet::default().counts_by_resolution().count();
+    assert_eq!(result, 0, "empty");
+}
+
+#[test]
+fn single_resolution() {
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let single = FrozenSet::new_single_resolution(set.as_bytes())
+        .expect("single resolution");
+    assert_eq!(single.resolution(), Some(Resolution::Seven), "resolution");
+
+    let parent = cell_index!(0x85318d83fffffff);
+    let queries = std::iter::once(parent)
+        .chain(parent.children(Resolution::Six))
+        .chain(parent.children(Resolution::Eight))
+        .chain(std::iter::once(cell_index!(0x8a1fb46622dffff)));
+    for cell in queries {
+        assert_eq!(single.contains(cell), set.contains(cell), "{cell}");
+    }
+
+    let set = FrozenSet::try_from_iter(vec![
+        cell_index!(0x85283473fffffff),
+        cell_index!(0x85318d83fffffff),
+        cell_index!(0x8631ac0c7ffffff),
+    ])
+    .expect("failed to create set");
+    let result = FrozenSet::new_single_resolution(set.as_bytes());
+    assert!(
+        matches!(result, Err(BuildError::MixedResolutions)),
+        "mixed resolutions"
+    );
+
+    let set = FrozenSet::default();
+    let single = FrozenSet::new_single_resolution(set.as_bytes())
+        .expect("single resolution");
+    assert!(single.contains(parent).is_none(), "empty set");
+}
+
+#[test]
+fn is_compacted() {
+    let parent = cell_index!(0x85318d83fffffff);
+
+    let set =
+        FrozenSet::try_from_iter(parent.children(Resolution::Six).skip(1))
+            .expect("failed to create set");
+    assert!(set.is_compacted(), "compacted");
+
+    let set =
+        FrozenSet::try_from_iter(vec![parent, cell_index!(0x86318d837ffffff)])
+            .expect("failed to create set");
+    assert!(!set.is_compacted(), "overlapping");
+
+    let set = FrozenSet::try_from_iter(parent.children(Resolution::Six))
+        .expect("failed to create set");
+    assert!(!set.is_compacted(), "expanded siblings");
+}
+
+#[test]
+fn minimal_cover() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let children = parent.children(Resolution::Six).collect::<Vec<_>>();
+    // Drop one grandchild of the second child.
+    let set = FrozenSet::try_from_iter(
+        test_cells()
+            .enumerate()
+            .filter_map(|(idx, cell)| (idx != 10).then_some(cell)),
+    )
+    .expect("failed to create set");
+
+    let result = set
+        .minimal_cover()
+        .expect("failed to cover")
+        .iter()
+        .collect::<Vec<_>>();
+    let mut expected = vec![children[0]];
+    expected.extend(
+        children[1]
+            .children(Resolution::Seven)
+            .enumerate()
+            .filter_map(|(idx, cell)| (idx != 3).then_some(cell)),
+    );
+    expected.extend(&children[2..]);
+    assert_eq!(result, expected);
+
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let result = set
+        .minimal_cover()
+        .expect("failed to cover")
+        .iter()
+        .collect::<Vec<_>>();
+    assert_eq!(result, vec![parent], "complete");
+}
+
+#[test]
+fn merge_one_level() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let children = parent.children(Resolution::Six).collect::<Vec<_>>();
+    // Drop one grandchild of the second child.
+    let set = FrozenSet::try_from_iter(
+        test_cells()
+            .enumerate()
+            .filter_map(|(idx, cell)| (idx != 10).then_some(cell)),
+    )
+    .expect("failed to create set");
+
+    let result = set
+        .merge_one_level()
+        .expect("failed to merge")
+        .iter()
+        .collect::<Vec<_>>();
+    let mut expected = vec![children[0]];
+    expected.extend(
+        children[1]
+            .children(Resolution::Seven)
+            .enumerate()
+            .filter_map(|(idx, cell)| (idx != 3).then_some(cell)),
+    );
+    expected.extend(&children[2..]);
+    assert_eq!(result, expected, "incomplete group kept");
+
+    // Complete at every level, but only merged once.
+    let set =
+        FrozenSet::try_from_iter(test_cells()).expect("failed to create set");
+    let result = set
+        .merge_one_level()
+        .expect("failed to merge")
+        .iter()
+        .collect::<Vec<_>>();
+    assert_eq!(result, children, "single level");
+}
+
+#[test]
+fn union_compacted() {
+    let parent = cell_index!(0x85318d83fffffff);
+    let a = FrozenSet::try_from_iter(std::iter::once(parent))
+        .expect("failed to create set");
+    let b =
+        FrozenSet::try_from_iter(parent.children(Resolution::Eight).take(5))
+            .expect("failed to create set");
+
+    let result = a
+        .union_compacted(&b)
+        .expect("failed to union")
+        .iter()
+        .collect::<Vec<_>>();
+    assert_eq!(result, vec![parent], "descendants dropped");
+
+    // Siblings split across both sets collapse into their parent.
+    let other = cell_index!(0x85318d93fffffff);
+    let a = FrozenSet::try_from_iter(
+        std::iter::once(parent).chain(other.children(Resolution::Six).take(2)),
+    )
+    .expect("failed to create set");
+    let b = FrozenSet::try_from_iter(other.children(Resolution::Six).skip(2))
+        .expect("failed to create set");
+
+    let result = a
+        .union_compacted(&b)
+        .expect("failed to union")
+        .iter()
+        .collect::<Vec<_>>();
+    assert_eq!(result, vec![parent, other], "siblings collapsed");
+}
+
 // -----------------------------------------------------------------------------
 
 fn test_cells() -> impl Iterator<Item = h3o::CellIndex> {